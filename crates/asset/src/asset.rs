@@ -1,12 +1,23 @@
-use ecs::core::{internal::blob::BlobCell, resource::Resource, Type};
-use hashbrown::HashMap;
+use ecs::core::{internal::blob::BlobCell, resource::Resource, IndexMap, Type};
 use serde::ser::SerializeStruct;
 use std::{hash::Hash, marker::PhantomData};
 use uuid::Uuid;
 
 pub trait Asset: Send + Sync + serde::Serialize + for<'a> serde::Deserialize<'a> + 'static {}
 
-pub trait Settings: Default + serde::Serialize + for<'a> serde::Deserialize<'a> + 'static {}
+/// Settings are stored in an asset's metadata. Fields missing from older metadata are
+/// filled in from `Default`, so adding a field doesn't need a new version.
+pub trait Settings: Default + serde::Serialize + for<'a> serde::Deserialize<'a> + 'static {
+    /// The version written to new metadata. Bump it when a change needs a migration.
+    fn version() -> u32 {
+        0
+    }
+
+    /// Upgrades settings written with an older `version` before they're deserialized.
+    fn migrate(_version: u32, settings: ron::Value) -> ron::Value {
+        settings
+    }
+}
 
 impl Asset for () {}
 impl Settings for () {}
@@ -248,8 +259,9 @@ impl<A: Asset, S: Settings> serde::Serialize for AssetMetadata<A, S> {
     where
         Ser: serde::Serializer,
     {
-        let mut object = ser.serialize_struct("Metadata", 2)?;
+        let mut object = ser.serialize_struct("Metadata", 3)?;
         object.serialize_field("id", &self.id)?;
+        object.serialize_field("version", &S::version())?;
         object.serialize_field("settings", &self.settings)?;
         object.end()
     }
@@ -264,6 +276,7 @@ impl<'de, A: Asset, S: Settings> serde::Deserialize<'de> for AssetMetadata<A, S>
         #[serde(field_identifier, rename_all = "snake_case")]
         enum Field {
             Id,
+            Version,
             Settings,
         }
 
@@ -281,6 +294,7 @@ impl<'de, A: Asset, S: Settings> serde::Deserialize<'de> for AssetMetadata<A, S>
                 V: serde::de::MapAccess<'de>,
             {
                 let mut id = None;
+                let mut version = None;
                 let mut settings = None;
 
                 while let Some(key) = map.next_key()? {
@@ -291,6 +305,12 @@ impl<'de, A: Asset, S: Settings> serde::Deserialize<'de> for AssetMetadata<A, S>
                             }
                             id = Some(map.next_value()?);
                         }
+                        Field::Version => {
+                            if version.is_some() {
+                                return Err(serde::de::Error::duplicate_field("version"));
+                            }
+                            version = Some(map.next_value::<u32>()?);
+                        }
                         Field::Settings => {
                             if settings.is_some() {
                                 return Err(serde::de::Error::duplicate_field("settings"));
@@ -301,9 +321,22 @@ impl<'de, A: Asset, S: Settings> serde::Deserialize<'de> for AssetMetadata<A, S>
                 }
 
                 let id = id.ok_or_else(|| serde::de::Error::missing_field("id"))?;
-                let settings =
+                let settings: ron::Value =
                     settings.ok_or_else(|| serde::de::Error::missing_field("settings"))?;
 
+                let version = version.unwrap_or(0);
+                let settings = match version < S::version() {
+                    true => S::migrate(version, settings),
+                    false => settings,
+                };
+
+                let defaults = ron::to_string(&S::default())
+                    .and_then(|defaults| Ok(ron::from_str::<ron::Value>(&defaults)?))
+                    .map_err(serde::de::Error::custom)?;
+                let settings = merge_defaults(defaults, settings)
+                    .into_rust::<S>()
+                    .map_err(serde::de::Error::custom)?;
+
                 Ok(AssetMetadata {
                     id,
                     settings,
@@ -312,19 +345,37 @@ impl<'de, A: Asset, S: Settings> serde::Deserialize<'de> for AssetMetadata<A, S>
             }
         }
 
-        const FIELDS: &[&str] = &["id", "settings"];
+        const FIELDS: &[&str] = &["id", "version", "settings"];
         de.deserialize_struct("Metadata", FIELDS, Visitor(std::marker::PhantomData))
     }
 }
 
+/// Fills the fields missing from `value` with the ones in `defaults`, recursing into nested maps.
+fn merge_defaults(defaults: ron::Value, value: ron::Value) -> ron::Value {
+    match (defaults, value) {
+        (ron::Value::Map(mut defaults), ron::Value::Map(value)) => {
+            for (key, value) in value.iter() {
+                let value = match defaults.remove(key) {
+                    Some(default) => merge_defaults(default, value.clone()),
+                    None => value.clone(),
+                };
+                defaults.insert(key.clone(), value);
+            }
+
+            ron::Value::Map(defaults)
+        }
+        (_, value) => value,
+    }
+}
+
 pub struct Assets<A: Asset> {
-    assets: HashMap<AssetId, A>,
+    assets: IndexMap<AssetId, A>,
 }
 
 impl<A: Asset> Assets<A> {
     pub fn new() -> Self {
         Self {
-            assets: HashMap::new(),
+            assets: IndexMap::new(),
         }
     }
 
@@ -341,7 +392,7 @@ impl<A: Asset> Assets<A> {
     }
 
     pub fn remove(&mut self, id: &AssetId) -> Option<A> {
-        self.assets.remove(id)
+        self.assets.shift_remove(id)
     }
 
     pub fn contains(&self, id: &AssetId) -> bool {
@@ -364,6 +415,10 @@ impl<A: Asset> Assets<A> {
         self.assets.iter_mut()
     }
 
+    pub fn ids(&self) -> impl Iterator<Item = &AssetId> {
+        self.assets.keys()
+    }
+
     pub fn clear(&mut self) {
         self.assets.clear()
     }
@@ -415,3 +470,97 @@ pub enum MetaMode {
     Text,
     Binary,
 }
+
+#[cfg(test)]
+mod tests {
+    use super::{Asset, AssetId, AssetMetadata, Assets, Settings};
+    use uuid::Uuid;
+
+    #[derive(Debug, Clone, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+    struct Number(u32);
+    impl Asset for Number {}
+
+    #[test]
+    fn iter_assets() {
+        let mut assets = Assets::<Number>::new();
+        let ids = [
+            AssetId::new::<Number>(),
+            AssetId::new::<Number>(),
+            AssetId::new::<Number>(),
+        ];
+
+        for (index, id) in ids.iter().enumerate() {
+            assets.add(*id, Number(index as u32));
+        }
+
+        assert_eq!(assets.len(), 3);
+        assert_eq!(assets.iter().count(), 3);
+        assert!(assets.ids().eq(ids.iter()));
+        assert!(assets
+            .iter()
+            .all(|(id, asset)| ids[asset.0 as usize] == *id));
+    }
+
+    #[derive(Debug, serde::Serialize, serde::Deserialize)]
+    struct ImageSettings {
+        srgb: bool,
+        mipmaps: u32,
+    }
+
+    impl Default for ImageSettings {
+        fn default() -> Self {
+            Self {
+                srgb: true,
+                mipmaps: 4,
+            }
+        }
+    }
+
+    impl Settings for ImageSettings {
+        fn version() -> u32 {
+            1
+        }
+
+        fn migrate(version: u32, settings: ron::Value) -> ron::Value {
+            let ron::Value::Map(mut settings) = settings else {
+                return settings;
+            };
+
+            if version == 0 {
+                let linear = settings.remove(&ron::Value::String("linear".into()));
+                if let Some(ron::Value::Bool(linear)) = linear {
+                    settings.insert(ron::Value::String("srgb".into()), ron::Value::Bool(!linear));
+                }
+            }
+
+            ron::Value::Map(settings)
+        }
+    }
+
+    #[test]
+    fn settings_missing_field() {
+        let id = Uuid::from_u128(1);
+        let metadata = AssetMetadata::<Number, ImageSettings>::new(id, ImageSettings::default());
+        let text = ron::to_string(&metadata).unwrap();
+        let text = text.replace("mipmaps:4", "");
+        assert!(!text.contains("mipmaps"));
+
+        let metadata = ron::from_str::<AssetMetadata<Number, ImageSettings>>(&text).unwrap();
+        assert_eq!(metadata.id(), &AssetId::from::<Number>(id));
+        assert!(metadata.srgb);
+        assert_eq!(metadata.mipmaps, 4);
+    }
+
+    #[test]
+    fn settings_migration() {
+        let id = AssetId::from::<Number>(Uuid::from_u128(1));
+        let text = format!(
+            "(id:{},settings:(linear:true,mipmaps:2))",
+            ron::to_string(&id).unwrap()
+        );
+
+        let metadata = ron::from_str::<AssetMetadata<Number, ImageSettings>>(&text).unwrap();
+        assert!(!metadata.srgb);
+        assert_eq!(metadata.mipmaps, 2);
+    }
+}
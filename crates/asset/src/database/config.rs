@@ -13,11 +13,13 @@ use crate::{
 };
 use ecs::{core::resource::Resource, world::action::WorldActionFn};
 use hashbrown::HashMap;
+use std::time::Duration;
 
 pub struct AssetConfig {
     registry: AssetRegistry,
     sources: AssetSources,
     cache: AssetCache,
+    unload_delay: Duration,
 }
 
 impl AssetConfig {
@@ -26,6 +28,7 @@ impl AssetConfig {
             registry: AssetRegistry::new(),
             sources: AssetSources::new(),
             cache: AssetCache::new(".cache"),
+            unload_delay: Duration::from_secs(1),
         }
     }
 
@@ -49,6 +52,15 @@ impl AssetConfig {
         &self.cache
     }
 
+    /// How long an asset stays loaded after its last strong handle is dropped.
+    pub fn unload_delay(&self) -> Duration {
+        self.unload_delay
+    }
+
+    pub fn set_unload_delay(&mut self, delay: Duration) {
+        self.unload_delay = delay;
+    }
+
     pub fn add_importer<I: Importer>(&mut self) {
         self.registry.add_importer::<I>();
     }
@@ -57,6 +69,16 @@ impl AssetConfig {
         self.sources.add(name.into(), io);
     }
 
+    /// Mounts `io` onto the source `name`. Higher priority mounts shadow lower ones.
+    pub fn mount_source<I: FileSystem>(
+        &mut self,
+        name: impl Into<AssetSourceName>,
+        io: I,
+        priority: i32,
+    ) {
+        self.sources.mount(name.into(), io, priority);
+    }
+
     pub fn embed_assets(&mut self, name: impl Into<AssetSourceName>, assets: EmbeddedFs) {
         self.sources.add(name.into(), assets);
     }
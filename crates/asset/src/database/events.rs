@@ -17,6 +17,8 @@ use hashbrown::HashSet;
 pub enum AssetEvent<A: Asset> {
     Imported(AssetId),
     Loaded(AssetId),
+    /// The asset was reloaded, either because it or one of its dependencies was reimported.
+    Modified(AssetId),
     Unloaded {
         id: AssetId,
         asset: Option<A>,
@@ -192,14 +194,23 @@ impl<A: Asset> AssetLoaded<A> {
 
 impl<A: Asset> WorldAction for AssetLoaded<A> {
     fn execute(self, world: &mut ecs::world::World) -> Option<()> {
-        world.resource_mut::<Assets<A>>().add(self.id, self.asset);
-        world
-            .resource_mut::<Events<AssetEvent<A>>>()
-            .add(AssetEvent::Loaded(self.id));
-
         let database = world.resource::<AssetDatabase>();
         let mut states = database.states.write_arc_blocking();
-        Some(states.loaded(self.id, self.dependencies, self.parent))
+
+        // The asset was unloaded after the load finished but before this ran.
+        if !states.load_state(self.id).is_loaded() {
+            return None;
+        }
+
+        states.loaded(self.id, self.dependencies, self.parent);
+
+        let event = match world.resource_mut::<Assets<A>>().add(self.id, self.asset) {
+            Some(_) => AssetEvent::Modified(self.id),
+            None => AssetEvent::Loaded(self.id),
+        };
+        world.resource_mut::<Events<AssetEvent<A>>>().add(event);
+
+        Some(())
     }
 }
 
@@ -267,9 +278,13 @@ impl<A: Asset> AssetUnloaded<A> {
 
 impl<A: Asset> WorldAction for AssetUnloaded<A> {
     fn execute(self, world: &mut ecs::world::World) -> Option<()> {
-        let asset = world.resource_mut::<Assets<A>>().remove(&self.id);
         let database = world.resource::<AssetDatabase>();
         let mut states = database.states.write_arc_blocking();
+        if states.unloading(self.id) {
+            return Some(());
+        }
+
+        let asset = world.resource_mut::<Assets<A>>().remove(&self.id);
         let state = states.unload(self.id)?;
 
         world
@@ -332,6 +347,9 @@ impl WorldAction for ReloadAssets {
         let database = world.resource::<AssetDatabase>();
         let states = database.states.read_arc_blocking();
 
+        let dependents = states.dependents_of(&self.ids);
+        self.ids.extend(dependents);
+
         let reloads = self.ids.drain(..).filter_map(|id| {
             let state = states.load_state(id);
             (state.is_loaded() || state.is_failed()).then_some(AssetLoadPath::Id(id))
@@ -1,7 +1,12 @@
-use crate::io::{
-    cache::{AssetLoadPath, SharedLibrary},
-    source::AssetPath,
-    AssetIoError,
+use crate::{
+    asset::{Asset, AssetId, AssetMetadata, AssetType},
+    handle::{AssetRefs, FolderHandle, Handle},
+    importer::SaveAsset,
+    io::{
+        cache::{AssetLoadPath, SharedLibrary},
+        source::{AssetPath, AssetSource},
+        AssetIoError,
+    },
 };
 use async_std::sync::{Mutex, RwLock};
 use config::AssetConfig;
@@ -27,6 +32,11 @@ pub enum DatabaseEvent {
     Import(Vec<AssetPath>),
     Refresh(RefreshMode),
     Load(Vec<AssetLoadPath>),
+    LoadFolder {
+        path: AssetPath,
+        recursive: bool,
+        handle: FolderHandle,
+    },
 }
 
 pub enum DatabaseInitError {
@@ -42,6 +52,7 @@ pub struct AssetDatabase {
     actions: WorldActions,
     library: SharedLibrary,
     states: SharedStates,
+    refs: Arc<AssetRefs>,
     state: Arc<Mutex<DatabaseState>>,
     events: Arc<Mutex<VecDeque<DatabaseEvent>>>,
 }
@@ -54,6 +65,7 @@ impl AssetDatabase {
             config: Arc::new(config),
             library: SharedLibrary::default(),
             states: SharedStates::default(),
+            refs: Arc::default(),
             state: Arc::new(Mutex::new(DatabaseState::Idle)),
             events: Arc::default(),
         }
@@ -86,6 +98,15 @@ impl AssetDatabase {
         &self.states
     }
 
+    pub fn refs(&self) -> &Arc<AssetRefs> {
+        &self.refs
+    }
+
+    /// Creates a strong handle to the asset, keeping it loaded until every strong handle is dropped.
+    pub fn handle<A: Asset>(&self, id: impl Into<AssetId>) -> Handle<A> {
+        Handle::strong(id.into(), self.refs.clone())
+    }
+
     pub fn refresh(&self, mode: RefreshMode) {
         self.events
             .lock_arc_blocking()
@@ -114,15 +135,99 @@ impl AssetDatabase {
         }
     }
 
+    /// Writes the asset and its metadata to `path`, then reimports it so the library
+    /// maps the path to `id` and picks up the new checksum.
+    pub async fn save<A: SaveAsset>(
+        &self,
+        id: AssetId,
+        asset: &A,
+        path: impl Into<AssetPath>,
+    ) -> Result<(), AssetIoError> {
+        let path = path.into();
+        if id.ty() != AssetType::of::<A>() {
+            let error = std::io::Error::new(
+                std::io::ErrorKind::InvalidInput,
+                format!("Asset {:?} is not a {}", id, std::any::type_name::<A>()),
+            );
+            return Err(AssetIoError::from(error));
+        }
+
+        let source = self.config.source(path.source()).ok_or_else(|| {
+            let error = std::io::Error::new(
+                std::io::ErrorKind::NotFound,
+                format!("Invalid source: {}", path),
+            );
+            AssetIoError::from(error)
+        })?;
+
+        let meta_path = AssetSource::metadata_path(path.path());
+        let metadata = match source.exists(&meta_path).await? {
+            true => source.load_metadata::<A, A::Settings>(path.path()).await?,
+            false => AssetMetadata::<A, A::Settings>::new(id.value(), Default::default()),
+        };
+
+        if metadata.id() != &id {
+            let error = std::io::Error::new(
+                std::io::ErrorKind::InvalidData,
+                format!(
+                    "{} belongs to asset {:?}, not {:?}",
+                    path,
+                    metadata.id(),
+                    id
+                ),
+            );
+            return Err(AssetIoError::from(error));
+        }
+
+        let parent = path.path().parent().filter(|p| !p.as_os_str().is_empty());
+        if let Some(parent) = parent {
+            source.create_dir_all(parent).await?;
+        }
+
+        let mut writer = source.writer(path.path()).await?;
+        asset
+            .save(writer.as_mut())
+            .await
+            .map_err(std::io::Error::other)?;
+        std::mem::drop(writer);
+
+        source.save_metadata(path.path(), &metadata).await?;
+
+        self.import([path]);
+
+        Ok(())
+    }
+
+    /// Loads every file in the folder that has a registered importer.
+    pub fn load_folder(&self, path: impl Into<AssetPath>, recursive: bool) -> FolderHandle {
+        let handle = FolderHandle::new();
+        self.events
+            .lock_arc_blocking()
+            .push_back(DatabaseEvent::LoadFolder {
+                path: path.into(),
+                recursive,
+                handle: handle.clone(),
+            });
+        self.update();
+
+        handle
+    }
+
     fn update(&self) {
         let mut state_lock = self.state.lock_arc_blocking();
         if *state_lock == DatabaseState::Idle {
             *state_lock = DatabaseState::Updating;
             let database = self.clone();
-            self.tasks.spawn(move || {
+            self.tasks.spawn(move || loop {
                 block_on(database.run());
+
+                // Events added after run returned would otherwise be left
+                // in the queue, since update only spawns a task when idle.
                 let mut state = database.state.lock_arc_blocking();
-                *state = DatabaseState::Idle;
+                if database.events.lock_arc_blocking().is_empty() {
+                    *state = DatabaseState::Idle;
+                    break;
+                }
             });
         }
     }
@@ -136,6 +241,14 @@ impl AssetDatabase {
                     DatabaseEvent::Refresh(mode) => AssetRefresher.refresh(mode, self).await,
                     DatabaseEvent::Import(paths) => AssetImporter.import(paths, self).await,
                     DatabaseEvent::Load(paths) => AssetLoader.load(paths, self).await,
+                    DatabaseEvent::LoadFolder {
+                        path,
+                        recursive,
+                        handle,
+                    } => {
+                        let ids = AssetLoader.load_folder(path, recursive, self).await;
+                        handle.resolve(ids);
+                    }
                 }
             }
 
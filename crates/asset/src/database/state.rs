@@ -1,5 +1,11 @@
+use super::AssetDatabase;
 use crate::asset::AssetId;
 use async_std::sync::RwLock;
+use ecs::{
+    core::IndexSet,
+    system::{SystemArg, WorldAccess},
+    world::cell::WorldCell,
+};
 use hashbrown::{hash_map::Entry, HashMap, HashSet};
 use std::sync::Arc;
 
@@ -9,6 +15,7 @@ pub enum LoadState {
     Loading,
     Loaded,
     Failed,
+    Unloading,
 }
 
 impl LoadState {
@@ -28,6 +35,10 @@ impl LoadState {
         matches!(self, LoadState::Unloaded)
     }
 
+    pub fn is_unloading(self) -> bool {
+        matches!(self, LoadState::Unloading)
+    }
+
     pub fn is_unloaded_or_failed(self) -> bool {
         matches!(self, LoadState::Unloaded | LoadState::Failed)
     }
@@ -206,7 +217,7 @@ impl AssetStates {
             for dep in dependencies {
                 self.states.get_mut(&id).unwrap().add_dependency(dep);
                 match self.states.get_mut(&dep) {
-                    Some(state) => state.add_dependency(id),
+                    Some(state) => state.add_dependent(id),
                     None => {
                         let mut state = AssetState::new();
                         state.add_dependent(id);
@@ -239,12 +250,67 @@ impl AssetStates {
         Some(state)
     }
 
+    /// Marks an in-flight load to be discarded once it finishes.
+    /// Returns false if the asset is not currently loading.
+    pub fn unloading(&mut self, id: AssetId) -> bool {
+        match self.states.get_mut(&id) {
+            Some(state) if state.state().is_loading() => {
+                state.set_state(LoadState::Unloading);
+                true
+            }
+            _ => false,
+        }
+    }
+
+    /// Removes the state of a load that was unloaded while in flight.
+    pub fn cancelled(&mut self, id: AssetId) -> bool {
+        if !self.load_state(id).is_unloading() {
+            return false;
+        }
+
+        self.unload(id);
+        true
+    }
+
     pub fn failed(&mut self, id: AssetId) -> &AssetState {
         let state = self.states.entry(id).or_insert_with(AssetState::new);
         state.set_state(LoadState::Failed);
         state
     }
 
+    /// Returns every asset that transitively depends on `ids`, ordered so that
+    /// each asset comes after its dependencies. Cycles are broken in discovery order.
+    pub fn dependents_of(&self, ids: &[AssetId]) -> Vec<AssetId> {
+        let mut found = IndexSet::new();
+        let mut stack = ids.to_vec();
+        while let Some(id) = stack.pop() {
+            let state = match self.states.get(&id) {
+                Some(state) => state,
+                None => continue,
+            };
+
+            for dependent in state.dependents() {
+                if !ids.contains(dependent) && found.insert(*dependent) {
+                    stack.push(*dependent);
+                }
+            }
+        }
+
+        let mut order = Vec::with_capacity(found.len());
+        while !found.is_empty() {
+            let ready = found.iter().position(|id| {
+                self.states
+                    .get(id)
+                    .is_none_or(|state| state.dependencies().iter().all(|dep| !found.contains(dep)))
+            });
+
+            let id = found.shift_remove_index(ready.unwrap_or(0)).unwrap();
+            order.push(id);
+        }
+
+        order
+    }
+
     pub fn remove_dependent(&mut self, id: AssetId, dependent: AssetId) {
         if let Some(state) = self.states.get_mut(&id) {
             state.remove_dependent(&dependent);
@@ -259,3 +325,44 @@ impl AssetStates {
 }
 
 pub type SharedStates = Arc<RwLock<AssetStates>>;
+
+pub struct AssetLoadStates<'a> {
+    states: &'a SharedStates,
+}
+
+impl<'a> AssetLoadStates<'a> {
+    pub fn new(states: &'a SharedStates) -> Self {
+        Self { states }
+    }
+
+    /// Returns `None` if the states are currently locked by the database.
+    pub fn load_state(&self, id: AssetId) -> Option<LoadState> {
+        self.states.try_read().map(|states| states.load_state(id))
+    }
+
+    pub fn all_loaded(&self, ids: &[AssetId]) -> bool {
+        match self.states.try_read() {
+            Some(states) => ids.iter().all(|id| states.load_state(*id).is_loaded()),
+            None => false,
+        }
+    }
+
+    pub fn any_failed(&self, ids: &[AssetId]) -> bool {
+        match self.states.try_read() {
+            Some(states) => ids.iter().any(|id| states.load_state(*id).is_failed()),
+            None => false,
+        }
+    }
+}
+
+impl SystemArg for AssetLoadStates<'_> {
+    type Item<'a> = AssetLoadStates<'a>;
+
+    fn get<'a>(world: &'a WorldCell) -> Self::Item<'a> {
+        AssetLoadStates::new(world.get().resource::<AssetDatabase>().states())
+    }
+
+    fn access() -> Vec<WorldAccess> {
+        vec![WorldAccess::resource::<AssetDatabase>()]
+    }
+}
@@ -6,7 +6,7 @@ use super::{
 };
 use crate::{
     asset::{Asset, AssetId, AssetMetadata, Settings},
-    importer::{ImportError, LoadError},
+    importer::{AssetResult, ImportError, LoadError},
     io::{
         cache::{
             ArtifactMeta, AssetCache, AssetInfo, AssetLoadPath, ErasedLoadedAsset, SharedLibrary,
@@ -116,7 +116,7 @@ impl AssetImporter {
         path: &AssetPath,
         config: &AssetConfig,
         library: &SharedLibrary,
-    ) -> Result<Vec<ImportedAsset>, ImportError> {
+    ) -> AssetResult<Vec<ImportedAsset>> {
         let source = match config.source(path.source()) {
             Some(source) => source,
             None => return Err(ImportError::InvalidSource { path: path.clone() }),
@@ -159,7 +159,7 @@ impl AssetImporter {
         asset: ImportedAsset,
         config: &AssetConfig,
         library: &SharedLibrary,
-    ) -> Result<ImportResult, ImportError> {
+    ) -> AssetResult<ImportResult> {
         let path = &asset.path;
         let id = asset.id;
         let source = match config.source(path.source()) {
@@ -321,7 +321,14 @@ impl AssetLoader {
                             let id = &asset.meta.id;
                             let deps = asset.meta.dependencies;
                             let parent = asset.meta.parent;
-                            states.write().await.loaded(*id, Some(deps), parent);
+                            {
+                                let mut states = states.write().await;
+                                if states.cancelled(*id) {
+                                    continue;
+                                }
+
+                                states.loaded(*id, Some(deps), parent);
+                            }
 
                             let states = states.read().await;
                             let state = states.get(id).unwrap();
@@ -352,7 +359,15 @@ impl AssetLoader {
                                     error,
                                     path: load_path,
                                 } => {
-                                    states.write().await.failed(id);
+                                    {
+                                        let mut states = states.write().await;
+                                        if states.cancelled(id) {
+                                            continue;
+                                        }
+
+                                        states.failed(id);
+                                    }
+
                                     let states = states.read().await;
                                     self.finish_loading(
                                         &id,
@@ -396,6 +411,79 @@ impl AssetLoader {
         }
     }
 
+    pub async fn load_folder(
+        &self,
+        path: AssetPath,
+        recursive: bool,
+        database: &AssetDatabase,
+    ) -> Vec<AssetId> {
+        let config = &database.config;
+        let library = &database.library;
+
+        let paths = match config.source(path.source()) {
+            Some(source) => self.scan_folder(path, recursive, source, config).await,
+            None => return vec![],
+        };
+
+        let missing = {
+            let library = library.read().await;
+            paths
+                .iter()
+                .filter(|path| library.get_id(path).is_none())
+                .cloned()
+                .collect::<Vec<_>>()
+        };
+
+        if !missing.is_empty() {
+            AssetImporter.import(missing, database).await;
+        }
+
+        let ids = {
+            let library = library.read().await;
+            paths
+                .iter()
+                .filter_map(|path| library.get_id(path))
+                .collect::<Vec<_>>()
+        };
+
+        let paths = ids.iter().map(|id| AssetLoadPath::Id(*id)).collect();
+        self.load(paths, database).await;
+
+        ids
+    }
+
+    async fn scan_folder(
+        &self,
+        asset_path: AssetPath,
+        recursive: bool,
+        source: &AssetSource,
+        config: &AssetConfig,
+    ) -> Vec<AssetPath> {
+        let mut paths = match source.read_dir(asset_path.path()).await {
+            Ok(paths) => paths,
+            Err(_) => return vec![],
+        };
+
+        let mut assets = vec![];
+        while let Some(path) = paths.next().await {
+            let path = AssetPath::new(asset_path.source().clone(), path);
+            match source.is_dir(path.path()).await {
+                Ok(true) if recursive => {
+                    let scan = Box::pin(self.scan_folder(path, recursive, source, config));
+                    assets.extend(scan.await);
+                }
+                Ok(false) => match path.ext() {
+                    Some("meta") | None => continue,
+                    Some(ext) if config.registry().get_by_ext(ext).is_some() => assets.push(path),
+                    _ => continue,
+                },
+                _ => continue,
+            }
+        }
+
+        assets
+    }
+
     async fn load_asset(
         &self,
         load_path: &AssetLoadPath,
@@ -797,18 +885,34 @@ impl Settings for Folder {}
 #[cfg(test)]
 mod tests {
     use crate::{
-        asset::{Asset, AssetMetadata},
-        database::{config::AssetConfig, events::AssetEvent, state::LoadState, AssetDatabase},
-        importer::{DefaultProcessor, ImportContext, Importer},
+        asset::{Asset, AssetMetadata, Assets},
+        asset::{AssetId, AssetType},
+        database::{
+            config::AssetConfig,
+            events::{AssetEvent, UnloadAssets},
+            state::{AssetLoadStates, LoadState},
+            AssetDatabase, DatabaseState,
+        },
+        handle::{FolderHandle, Handle},
+        importer::{DefaultProcessor, ImportContext, ImportError, Importer, SaveAsset},
         io::{
-            cache::AssetCache, source::AssetSourceName, vfs::VirtualFs, AssetIoError, AssetReader,
-            FileSystem,
+            cache::AssetCache,
+            source::{AssetPath, AssetSourceName},
+            vfs::VirtualFs,
+            AssetIoError, AssetReader, AssetWriter, FileSystem,
         },
         plugin::{AssetExt, AssetPlugin},
+        server::AssetServer,
+    };
+    use ecs::{
+        core::resource::Res, event::Events, system::local::Local, world::action::WorldActions,
     };
-    use ecs::{core::resource::Res, event::Events, world::action::WorldActions};
     use futures_lite::{future::block_on, AsyncReadExt, AsyncWriteExt};
-    use game::{ExitGame, Game, GameBuilder, PostInit};
+    use game::{ExitGame, Game, GameBuilder, PostInit, Update};
+    use std::sync::{
+        atomic::{AtomicBool, AtomicU8, Ordering},
+        Mutex,
+    };
     use uuid::Uuid;
 
     #[derive(Debug, Clone, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
@@ -837,6 +941,7 @@ mod tests {
 
     trait TestAssetGame {
         fn set_test_cache(&mut self) -> &mut Self;
+        fn set_unload_delay(&mut self, delay: std::time::Duration) -> &mut Self;
     }
 
     impl TestAssetGame for GameBuilder {
@@ -845,6 +950,11 @@ mod tests {
                 .set_cache(AssetCache::test());
             self
         }
+
+        fn set_unload_delay(&mut self, delay: std::time::Duration) -> &mut Self {
+            self.resource_mut::<AssetConfig>().set_unload_delay(delay);
+            self
+        }
     }
 
     fn test_runner(mut game: Game) {
@@ -868,15 +978,19 @@ mod tests {
 
     async fn create_vfs() -> VirtualFs {
         let fs = VirtualFs::new("");
-        let mut writer = fs.writer("test.txt".as_ref()).await.unwrap();
-        writer.write(b"Hello, World!").await.unwrap();
+        write_text(&fs, "test.txt", "Hello, World!", ID).await;
+        fs
+    }
+
+    async fn write_text(fs: &VirtualFs, path: &str, text: &str, id: Uuid) {
+        let mut writer = fs.writer(path.as_ref()).await.unwrap();
+        writer.write(text.as_bytes()).await.unwrap();
 
-        let metadata = AssetMetadata::<PlainText, ()>::new(ID, ());
+        let metadata = AssetMetadata::<PlainText, ()>::new(id, ());
         let metadata = ron::to_string(&metadata).unwrap();
-        let mut meta_writer = fs.writer("test.txt.meta".as_ref()).await.unwrap();
+        let meta_path = format!("{}.meta", path);
+        let mut meta_writer = fs.writer(meta_path.as_ref()).await.unwrap();
         meta_writer.write(metadata.as_bytes()).await.unwrap();
-
-        fs
     }
 
     const ID: Uuid = Uuid::from_u128(0);
@@ -941,4 +1055,585 @@ mod tests {
             )
             .run();
     }
+
+    #[test]
+    fn load_folder() {
+        static HANDLE: Mutex<Option<FolderHandle>> = Mutex::new(None);
+
+        let file_system = block_on(async {
+            let fs = VirtualFs::new("");
+            fs.create_dir_all("textures".as_ref()).await.unwrap();
+            write_text(&fs, "a.txt", "A", Uuid::from_u128(1)).await;
+            write_text(&fs, "b.txt", "B", Uuid::from_u128(2)).await;
+            write_text(&fs, "textures/c.txt", "C", Uuid::from_u128(3)).await;
+            let mut writer = fs.writer("textures/d.png".as_ref()).await.unwrap();
+            writer.write(&[0, 1, 2, 3]).await.unwrap();
+            fs
+        });
+
+        Game::new()
+            .add_plugin(AssetPlugin)
+            .register_asset::<PlainText>()
+            .add_importer::<PlainText>()
+            .add_asset_source(AssetSourceName::Default, file_system)
+            .set_runner(test_runner)
+            .set_test_cache()
+            .add_systems(PostInit, |db: Res<AssetDatabase>| {
+                *HANDLE.lock().unwrap() = Some(db.load_folder("", true));
+            })
+            .add_systems(Update, |db: Res<AssetDatabase>, actions: &WorldActions| {
+                let ids = match HANDLE.lock().unwrap().as_ref().and_then(|h| h.ids()) {
+                    Some(ids) => ids,
+                    None => return,
+                };
+
+                assert_eq!(ids.len(), 3);
+                let states = db.states().read_blocking();
+                for id in ids {
+                    assert_eq!(id.ty(), AssetType::of::<PlainText>());
+                    assert_eq!(states.load_state(id), LoadState::Loaded);
+                }
+
+                actions.add(ExitGame::Success);
+            })
+            .run();
+    }
+
+    #[derive(Debug, Clone, Copy, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+    struct Number(u32);
+    impl Asset for Number {}
+
+    impl Importer for Number {
+        type Asset = Number;
+        type Settings = ();
+        type Processor = DefaultProcessor<Self, Self::Settings>;
+        type Error = std::num::ParseIntError;
+
+        async fn import(
+            _ctx: &mut ImportContext<'_, Self::Asset, Self::Settings>,
+            reader: &mut dyn AssetReader,
+        ) -> Result<Self::Asset, Self::Error> {
+            let mut data = String::new();
+            let _ = reader.read_to_string(&mut data).await;
+            data.trim().parse().map(Number)
+        }
+
+        fn extensions() -> &'static [&'static str] {
+            &["num"]
+        }
+    }
+
+    #[test]
+    fn import_error_source() {
+        let file_system = block_on(async {
+            let fs = VirtualFs::new("");
+            let mut writer = fs.writer("bad.num".as_ref()).await.unwrap();
+            writer.write(b"not a number").await.unwrap();
+            fs
+        });
+
+        Game::new()
+            .add_plugin(AssetPlugin)
+            .register_asset::<Number>()
+            .add_importer::<Number>()
+            .add_asset_source(AssetSourceName::Default, file_system)
+            .set_runner(test_runner)
+            .set_test_cache()
+            .observe::<ImportError, _>(
+                |errors: Res<Events<ImportError>>, actions: &WorldActions| {
+                    use std::error::Error;
+
+                    for error in errors.iter() {
+                        let path = error.path_or_id().0.map(|p| p.path());
+                        assert_eq!(path.and_then(|p| p.to_str()), Some("bad.num"));
+                        assert!(error.to_string().contains("bad.num"));
+
+                        let source = error.source().expect("import error without a source");
+                        assert!(source.downcast_ref::<std::num::ParseIntError>().is_some());
+                        actions.add(ExitGame::Success);
+                    }
+                },
+            )
+            .run();
+    }
+
+    impl SaveAsset for PlainText {
+        type Settings = ();
+        type Error = AssetIoError;
+
+        async fn save(&self, writer: &mut dyn AssetWriter) -> Result<(), Self::Error> {
+            writer.write_all(self.0.as_bytes()).await?;
+            Ok(())
+        }
+    }
+
+    #[test]
+    fn save_asset() {
+        const SAVED_ID: Uuid = Uuid::from_u128(7);
+        const OTHER_ID: Uuid = Uuid::from_u128(8);
+        let file_system = VirtualFs::new("");
+        let saved = file_system.clone();
+        let other = file_system.clone();
+
+        Game::new()
+            .add_plugin(AssetPlugin)
+            .register_asset::<PlainText>()
+            .add_importer::<PlainText>()
+            .add_asset_source(AssetSourceName::Default, file_system)
+            .set_runner(test_runner)
+            .set_test_cache()
+            .add_systems(PostInit, move |db: Res<AssetDatabase>| {
+                let id = AssetId::from::<PlainText>(SAVED_ID);
+                let asset = PlainText("Saved text".to_string());
+
+                block_on(write_text(&other, "notes/other.txt", "Other", OTHER_ID));
+                assert!(block_on(db.save(id, &asset, "notes/other.txt")).is_err());
+                let text = block_on(async {
+                    let mut reader = other.reader("notes/other.txt".as_ref()).await.unwrap();
+                    let mut text = String::new();
+                    reader.read_to_string(&mut text).await.unwrap();
+                    text
+                });
+                assert_eq!(text, "Other");
+
+                block_on(db.save(id, &asset, "notes/saved.txt")).unwrap();
+            })
+            .observe::<AssetEvent<PlainText>, _>(
+                move |events: Res<Events<AssetEvent<PlainText>>>,
+                      database: Res<AssetDatabase>,
+                      actions: &WorldActions| {
+                    let saved_id = AssetId::from::<PlainText>(SAVED_ID);
+                    for event in events.iter() {
+                        if let AssetEvent::Imported(id) = event {
+                            if *id != saved_id {
+                                continue;
+                            }
+
+                            let library = database.library().read_blocking();
+                            let path = library.get_path(id).map(|p| p.path());
+                            assert_eq!(path.and_then(|p| p.to_str()), Some("notes/saved.txt"));
+
+                            let text = block_on(async {
+                                let path = "notes/saved.txt".as_ref();
+                                let mut reader = saved.reader(path).await.unwrap();
+                                let mut text = String::new();
+                                reader.read_to_string(&mut text).await.unwrap();
+                                text
+                            });
+                            assert_eq!(text, "Saved text");
+                            actions.add(ExitGame::Success);
+                        }
+                    }
+                },
+            )
+            .run();
+    }
+
+    #[test]
+    fn load_states() {
+        const OTHER_ID: Uuid = Uuid::from_u128(1);
+        let file_system = block_on(async {
+            let fs = create_vfs().await;
+            write_text(&fs, "other.txt", "Goodbye, World!", OTHER_ID).await;
+            fs
+        });
+
+        let ids = [
+            AssetId::from::<PlainText>(ID),
+            AssetId::from::<PlainText>(OTHER_ID),
+        ];
+
+        Game::new()
+            .add_plugin(AssetPlugin)
+            .register_asset::<PlainText>()
+            .add_importer::<PlainText>()
+            .add_asset_source(AssetSourceName::Default, file_system)
+            .set_runner(test_runner)
+            .set_test_cache()
+            .add_systems(PostInit, |db: Res<AssetDatabase>| db.load(["test.txt"]))
+            .add_systems(
+                Update,
+                move |states: AssetLoadStates,
+                      database: Res<AssetDatabase>,
+                      mut requested: Local<bool>,
+                      actions: &WorldActions| {
+                    if !*requested && states.load_state(ids[0]) == Some(LoadState::Loaded) {
+                        assert!(!states.all_loaded(&ids));
+                        database.load(["other.txt"]);
+                        *requested = true;
+                    } else if states.all_loaded(&ids) {
+                        for id in ids {
+                            assert_eq!(states.load_state(id), Some(LoadState::Loaded));
+                        }
+                        actions.add(ExitGame::Success);
+                    }
+
+                    assert!(!states.any_failed(&ids));
+                },
+            )
+            .run();
+    }
+
+    #[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
+    struct TextBundle(usize);
+    impl Asset for TextBundle {}
+
+    impl Importer for TextBundle {
+        type Asset = TextBundle;
+        type Settings = ();
+        type Processor = DefaultProcessor<Self, Self::Settings>;
+        type Error = AssetIoError;
+
+        async fn import(
+            ctx: &mut ImportContext<'_, Self::Asset, Self::Settings>,
+            reader: &mut dyn AssetReader,
+        ) -> Result<Self::Asset, Self::Error> {
+            let mut data = String::new();
+            reader.read_to_string(&mut data).await?;
+
+            let lines = data.lines().collect::<Vec<_>>();
+            for (index, line) in lines.iter().enumerate() {
+                let (label, text) = line.split_once('=').unwrap();
+                let id = AssetId::from::<PlainText>(Uuid::from_u128(10 + index as u128));
+                ctx.add_sub_asset(id, label, PlainText(text.to_string()))
+                    .map_err(AssetIoError::from)?;
+            }
+
+            Ok(TextBundle(lines.len()))
+        }
+
+        fn extensions() -> &'static [&'static str] {
+            &["bundle"]
+        }
+    }
+
+    #[test]
+    fn load_labeled_sub_assets() {
+        const BUNDLE_ID: Uuid = Uuid::from_u128(2);
+        let file_system = block_on(async {
+            let fs = VirtualFs::new("");
+            let mut writer = fs.writer("text.bundle".as_ref()).await.unwrap();
+            writer.write(b"first=Hello\nsecond=World").await.unwrap();
+
+            let metadata = AssetMetadata::<TextBundle, ()>::new(BUNDLE_ID, ());
+            let metadata = ron::to_string(&metadata).unwrap();
+            let mut meta_writer = fs.writer("text.bundle.meta".as_ref()).await.unwrap();
+            meta_writer.write(metadata.as_bytes()).await.unwrap();
+            fs
+        });
+
+        Game::new()
+            .add_plugin(AssetPlugin)
+            .register_asset::<PlainText>()
+            .register_asset::<TextBundle>()
+            .add_importer::<TextBundle>()
+            .add_asset_source(AssetSourceName::Default, file_system)
+            .set_runner(test_runner)
+            .set_test_cache()
+            .add_systems(PostInit, |db: Res<AssetDatabase>| {
+                db.load(["text.bundle@first", "text.bundle@second"])
+            })
+            .add_systems(
+                Update,
+                |database: Res<AssetDatabase>,
+                 texts: Res<Assets<PlainText>>,
+                 actions: &WorldActions| {
+                    let library = database.library().read_blocking();
+                    let first = library.get_id(&AssetPath::from_str("text.bundle@first"));
+                    let second = library.get_id(&AssetPath::from_str("text.bundle@second"));
+                    let (Some(first), Some(second)) = (first, second) else {
+                        return;
+                    };
+
+                    assert_ne!(first, second);
+                    if let (Some(first), Some(second)) = (texts.get(&first), texts.get(&second)) {
+                        assert_eq!(first, &PlainText("Hello".to_string()));
+                        assert_eq!(second, &PlainText("World".to_string()));
+                        actions.add(ExitGame::Success);
+                    }
+                },
+            )
+            .run();
+    }
+
+    static SLOW_LOAD_STARTED: AtomicBool = AtomicBool::new(false);
+    static SLOW_LOAD_RELEASED: AtomicBool = AtomicBool::new(false);
+
+    /// Blocks deserialization, and so the load task, until the test releases it.
+    #[derive(Debug, Clone, serde::Serialize)]
+    struct SlowText(String);
+    impl Asset for SlowText {}
+
+    impl<'de> serde::Deserialize<'de> for SlowText {
+        fn deserialize<D: serde::Deserializer<'de>>(de: D) -> Result<Self, D::Error> {
+            SLOW_LOAD_STARTED.store(true, Ordering::Release);
+            while !SLOW_LOAD_RELEASED.load(Ordering::Acquire) {
+                std::thread::sleep(std::time::Duration::from_millis(1));
+            }
+
+            String::deserialize(de).map(SlowText)
+        }
+    }
+
+    impl Importer for SlowText {
+        type Asset = SlowText;
+        type Settings = ();
+        type Processor = DefaultProcessor<Self, Self::Settings>;
+        type Error = AssetIoError;
+
+        async fn import(
+            _ctx: &mut ImportContext<'_, Self::Asset, Self::Settings>,
+            reader: &mut dyn AssetReader,
+        ) -> Result<Self::Asset, Self::Error> {
+            let mut data = String::new();
+            reader.read_to_string(&mut data).await?;
+            Ok(SlowText(data))
+        }
+
+        fn extensions() -> &'static [&'static str] {
+            &["slow"]
+        }
+    }
+
+    #[test]
+    fn unload_while_loading() {
+        const SLOW_ID: Uuid = Uuid::from_u128(3);
+        static STEP: AtomicU8 = AtomicU8::new(0);
+
+        let file_system = block_on(async {
+            let fs = VirtualFs::new("");
+            let mut writer = fs.writer("test.slow".as_ref()).await.unwrap();
+            writer.write(b"Hello, World!").await.unwrap();
+
+            let metadata = AssetMetadata::<SlowText, ()>::new(SLOW_ID, ());
+            let metadata = ron::to_string(&metadata).unwrap();
+            let mut meta_writer = fs.writer("test.slow.meta".as_ref()).await.unwrap();
+            meta_writer.write(metadata.as_bytes()).await.unwrap();
+            fs
+        });
+
+        let id = AssetId::from::<SlowText>(SLOW_ID);
+
+        Game::new()
+            .add_plugin(AssetPlugin)
+            .register_asset::<SlowText>()
+            .add_importer::<SlowText>()
+            .add_asset_source(AssetSourceName::Default, file_system)
+            .set_runner(test_runner)
+            .set_test_cache()
+            .add_systems(PostInit, |db: Res<AssetDatabase>| db.load(["test.slow"]))
+            .add_systems(
+                Update,
+                move |database: Res<AssetDatabase>,
+                      assets: Res<Assets<SlowText>>,
+                      actions: &WorldActions| {
+                    let state = database.states().read_blocking().load_state(id);
+                    match STEP.load(Ordering::Relaxed) {
+                        0 if SLOW_LOAD_STARTED.load(Ordering::Acquire) => {
+                            assert_eq!(state, LoadState::Loading);
+                            actions.add(UnloadAssets::new(vec![id]));
+                            STEP.store(1, Ordering::Relaxed);
+                        }
+                        1 => {
+                            assert_eq!(state, LoadState::Unloading);
+                            SLOW_LOAD_RELEASED.store(true, Ordering::Release);
+                            STEP.store(2, Ordering::Relaxed);
+                        }
+                        2 if database.state() == DatabaseState::Idle => {
+                            STEP.store(3, Ordering::Relaxed);
+                        }
+                        3 => {
+                            assert_eq!(state, LoadState::Unloaded);
+                            assert!(assets.get(&id).is_none());
+                            actions.add(ExitGame::Success);
+                        }
+                        _ => (),
+                    }
+                },
+            )
+            .run();
+    }
+
+    #[test]
+    fn unload_unreferenced_asset() {
+        static STEP: AtomicU8 = AtomicU8::new(0);
+
+        let file_system = block_on(create_vfs());
+        let id = AssetId::from::<PlainText>(ID);
+
+        Game::new()
+            .add_plugin(AssetPlugin)
+            .register_asset::<PlainText>()
+            .add_importer::<PlainText>()
+            .add_asset_source(AssetSourceName::Default, file_system)
+            .set_runner(test_runner)
+            .set_test_cache()
+            .set_unload_delay(std::time::Duration::ZERO)
+            .add_systems(PostInit, |db: Res<AssetDatabase>| db.load(["test.txt"]))
+            .add_systems(
+                Update,
+                move |database: Res<AssetDatabase>,
+                      texts: Res<Assets<PlainText>>,
+                      mut handles: Local<Vec<Handle<PlainText>>>,
+                      actions: &WorldActions| {
+                    match STEP.load(Ordering::Relaxed) {
+                        0 if texts.get(&id).is_some() => {
+                            let handle = database.handle::<PlainText>(id);
+                            assert!(!handle.downgrade().is_strong());
+                            handles.push(handle.clone());
+                            handles.push(handle);
+                            assert_eq!(database.refs().count(&id), 2);
+                            STEP.store(1, Ordering::Relaxed);
+                        }
+                        1 => {
+                            handles.pop();
+                            STEP.store(2, Ordering::Relaxed);
+                        }
+                        2 => {
+                            assert!(texts.get(&id).is_some());
+                            assert_eq!(database.refs().count(&id), 1);
+                            handles.pop();
+                            STEP.store(3, Ordering::Relaxed);
+                        }
+                        3 if texts.get(&id).is_none() => {
+                            let state = database.states().read_blocking().load_state(id);
+                            assert_eq!(state, LoadState::Unloaded);
+                            actions.add(ExitGame::Success);
+                        }
+                        _ => (),
+                    }
+                },
+            )
+            .run();
+    }
+
+    #[test]
+    fn load_with_server() {
+        let file_system = block_on(create_vfs());
+
+        Game::new()
+            .add_plugin(AssetPlugin)
+            .register_asset::<PlainText>()
+            .add_importer::<PlainText>()
+            .add_asset_source(AssetSourceName::Default, file_system)
+            .set_runner(test_runner)
+            .set_test_cache()
+            .add_systems(
+                Update,
+                |server: AssetServer,
+                 mut handle: Local<Option<Handle<PlainText>>>,
+                 actions: &WorldActions| {
+                    match handle.as_ref() {
+                        Some(handle) => {
+                            if let Some(text) = server.get(handle) {
+                                assert_eq!(text, &PlainText("Hello, World!".to_string()));
+                                assert_eq!(server.load_state(handle), LoadState::Loaded);
+                                actions.add(ExitGame::Success);
+                            }
+                        }
+                        None => *handle = server.load::<PlainText>("test.txt").ok(),
+                    }
+                },
+            )
+            .run();
+    }
+
+    #[derive(Debug, Clone, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+    struct Material(u128);
+    impl Asset for Material {}
+
+    impl Importer for Material {
+        type Asset = Material;
+        type Settings = ();
+        type Processor = DefaultProcessor<Self, Self::Settings>;
+        type Error = AssetIoError;
+
+        async fn import(
+            ctx: &mut ImportContext<'_, Self::Asset, Self::Settings>,
+            reader: &mut dyn AssetReader,
+        ) -> Result<Self::Asset, Self::Error> {
+            let mut data = String::new();
+            reader.read_to_string(&mut data).await?;
+
+            let shader = data.trim().parse::<u128>().unwrap();
+            ctx.add_dependency(AssetId::from::<PlainText>(Uuid::from_u128(shader)));
+            Ok(Material(shader))
+        }
+
+        fn extensions() -> &'static [&'static str] {
+            &["mat"]
+        }
+    }
+
+    #[test]
+    fn reload_dependents() {
+        const SHADER_ID: Uuid = Uuid::from_u128(20);
+        const MATERIAL_IDS: [Uuid; 2] = [Uuid::from_u128(21), Uuid::from_u128(22)];
+        static STEP: AtomicU8 = AtomicU8::new(0);
+        static MODIFIED: Mutex<Vec<AssetId>> = Mutex::new(Vec::new());
+
+        let file_system = block_on(async {
+            let fs = VirtualFs::new("");
+            write_text(&fs, "shader.txt", "Original", SHADER_ID).await;
+            for (index, id) in MATERIAL_IDS.iter().enumerate() {
+                let path = format!("{}.mat", index);
+                let mut writer = fs.writer(path.as_ref()).await.unwrap();
+                writer.write(b"20").await.unwrap();
+
+                let metadata = AssetMetadata::<Material, ()>::new(*id, ());
+                let metadata = ron::to_string(&metadata).unwrap();
+                let meta_path = format!("{}.meta", path);
+                let mut meta_writer = fs.writer(meta_path.as_ref()).await.unwrap();
+                meta_writer.write(metadata.as_bytes()).await.unwrap();
+            }
+            fs
+        });
+
+        let shader = AssetId::from::<PlainText>(SHADER_ID);
+        let materials = MATERIAL_IDS.map(AssetId::from::<Material>);
+        let source = file_system.clone();
+
+        Game::new()
+            .add_plugin(AssetPlugin)
+            .register_asset::<PlainText>()
+            .add_importer::<PlainText>()
+            .add_importer::<Material>()
+            .add_asset_source(AssetSourceName::Default, file_system)
+            .set_runner(test_runner)
+            .set_test_cache()
+            .add_systems(PostInit, |db: Res<AssetDatabase>| {
+                db.load(["0.mat", "1.mat"])
+            })
+            .add_systems(
+                Update,
+                move |database: Res<AssetDatabase>,
+                      texts: Res<Assets<PlainText>>,
+                      loaded: Res<Assets<Material>>,
+                      actions: &WorldActions| {
+                    let ready =
+                        texts.contains(&shader) && materials.iter().all(|id| loaded.contains(id));
+                    if STEP.load(Ordering::Relaxed) == 0 && ready {
+                        block_on(write_text(&source, "shader.txt", "Modified", SHADER_ID));
+                        database.import(["shader.txt"]);
+                        STEP.store(1, Ordering::Relaxed);
+                    }
+
+                    let modified = MODIFIED.lock().unwrap();
+                    let text = texts.get(&shader);
+                    if materials.iter().all(|id| modified.contains(id))
+                        && text == Some(&PlainText("Modified".to_string()))
+                    {
+                        actions.add(ExitGame::Success);
+                    }
+                },
+            )
+            .observe::<AssetEvent<Material>, _>(|events: Res<Events<AssetEvent<Material>>>| {
+                for event in events.iter() {
+                    if let AssetEvent::Modified(id) = event {
+                        MODIFIED.lock().unwrap().push(*id);
+                    }
+                }
+            })
+            .run();
+    }
 }
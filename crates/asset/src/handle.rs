@@ -0,0 +1,184 @@
+use crate::asset::{Asset, AssetId};
+use hashbrown::{hash_map::Entry, HashMap};
+use std::{
+    hash::Hash,
+    sync::{Arc, Mutex},
+    time::{Duration, Instant},
+};
+
+/// Counts the strong handles of each asset.
+/// Assets whose last strong handle dropped are tracked until they are unloaded.
+#[derive(Default)]
+pub struct AssetRefs {
+    counts: Mutex<HashMap<AssetId, usize>>,
+    released: Mutex<HashMap<AssetId, Instant>>,
+}
+
+impl AssetRefs {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn count(&self, id: &AssetId) -> usize {
+        self.counts.lock().unwrap().get(id).copied().unwrap_or(0)
+    }
+
+    fn acquire(&self, id: AssetId) {
+        let mut counts = self.counts.lock().unwrap();
+        *counts.entry(id).or_default() += 1;
+        self.released.lock().unwrap().remove(&id);
+    }
+
+    fn release(&self, id: AssetId) {
+        let mut counts = self.counts.lock().unwrap();
+        if let Entry::Occupied(mut entry) = counts.entry(id) {
+            *entry.get_mut() -= 1;
+            if *entry.get() == 0 {
+                entry.remove();
+                self.released.lock().unwrap().insert(id, Instant::now());
+            }
+        }
+    }
+
+    /// Takes the assets that have had no strong handles for at least `delay`.
+    pub fn take_released(&self, delay: Duration) -> Vec<AssetId> {
+        let mut released = self.released.lock().unwrap();
+        let expired = released
+            .iter()
+            .filter(|(_, time)| time.elapsed() >= delay)
+            .map(|(id, _)| *id)
+            .collect::<Vec<_>>();
+
+        for id in &expired {
+            released.remove(id);
+        }
+
+        expired
+    }
+}
+
+/// A reference to an asset. Strong handles keep the asset loaded, and the asset is
+/// queued for unload once the last strong handle is dropped. Weak handles don't.
+pub struct Handle<A: Asset> {
+    id: AssetId,
+    refs: Option<Arc<AssetRefs>>,
+    _marker: std::marker::PhantomData<A>,
+}
+
+impl<A: Asset> Handle<A> {
+    pub fn strong(id: AssetId, refs: Arc<AssetRefs>) -> Self {
+        refs.acquire(id);
+        Self {
+            id,
+            refs: Some(refs),
+            _marker: std::marker::PhantomData,
+        }
+    }
+
+    pub fn weak(id: AssetId) -> Self {
+        Self {
+            id,
+            refs: None,
+            _marker: std::marker::PhantomData,
+        }
+    }
+
+    pub fn id(&self) -> AssetId {
+        self.id
+    }
+
+    pub fn is_strong(&self) -> bool {
+        self.refs.is_some()
+    }
+
+    pub fn downgrade(&self) -> Self {
+        Self::weak(self.id)
+    }
+}
+
+impl<A: Asset> Clone for Handle<A> {
+    fn clone(&self) -> Self {
+        match &self.refs {
+            Some(refs) => Self::strong(self.id, refs.clone()),
+            None => Self::weak(self.id),
+        }
+    }
+}
+
+impl<A: Asset> Drop for Handle<A> {
+    fn drop(&mut self) {
+        if let Some(refs) = &self.refs {
+            refs.release(self.id);
+        }
+    }
+}
+
+impl<A: Asset> std::fmt::Debug for Handle<A> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("Handle")
+            .field("id", &self.id)
+            .field("strong", &self.is_strong())
+            .finish()
+    }
+}
+
+impl<A: Asset> PartialEq for Handle<A> {
+    fn eq(&self, other: &Self) -> bool {
+        self.id == other.id
+    }
+}
+
+impl<A: Asset> Eq for Handle<A> {}
+
+impl<A: Asset> Hash for Handle<A> {
+    fn hash<H: std::hash::Hasher>(&self, state: &mut H) {
+        self.id.hash(state);
+    }
+}
+
+impl<A: Asset> From<&Handle<A>> for AssetId {
+    fn from(handle: &Handle<A>) -> Self {
+        handle.id
+    }
+}
+
+/// Resolves to the ids of the assets loaded by [`crate::database::AssetDatabase::load_folder`]
+/// once every asset in the folder has finished loading.
+#[derive(Clone, Default)]
+pub struct FolderHandle {
+    ids: Arc<Mutex<Option<Vec<AssetId>>>>,
+}
+
+impl FolderHandle {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn is_loaded(&self) -> bool {
+        self.ids.lock().unwrap().is_some()
+    }
+
+    pub fn ids(&self) -> Option<Vec<AssetId>> {
+        self.ids.lock().unwrap().clone()
+    }
+
+    pub(crate) fn resolve(&self, ids: Vec<AssetId>) {
+        *self.ids.lock().unwrap() = Some(ids);
+    }
+}
+
+impl std::fmt::Debug for FolderHandle {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("FolderHandle")
+            .field("ids", &self.ids())
+            .finish()
+    }
+}
+
+impl PartialEq for FolderHandle {
+    fn eq(&self, other: &Self) -> bool {
+        Arc::ptr_eq(&self.ids, &other.ids)
+    }
+}
+
+impl Eq for FolderHandle {}
@@ -3,7 +3,7 @@ use crate::{
     io::{
         cache::{Artifact, ArtifactMeta, AssetCache, AssetInfo, AssetLoadPath, LoadedAsset},
         source::{AssetPath, AssetSource},
-        AssetIoError, AssetReader,
+        AssetIoError, AssetReader, AssetWriter,
     },
 };
 use ecs::event::Event;
@@ -147,6 +147,17 @@ impl<A: Asset, S: Settings> Processor for DefaultProcessor<A, S> {
     }
 }
 
+/// Writes an asset back to its source format so it can be saved with
+/// [`crate::database::AssetDatabase::save`] and imported again.
+pub trait SaveAsset: Asset {
+    type Settings: Settings;
+    type Error: Error + Send + Sync + 'static;
+
+    fn save(&self, writer: &mut dyn AssetWriter) -> impl Future<Output = Result<(), Self::Error>>;
+}
+
+pub type AssetResult<T, E = ImportError> = Result<T, E>;
+
 #[derive(Debug)]
 pub enum ImportError {
     MissingExtension {
@@ -219,11 +230,38 @@ impl ImportError {
 
 impl std::fmt::Display for ImportError {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        std::fmt::Debug::fmt(self, f)
+        match self {
+            ImportError::MissingExtension { path } => write!(f, "Missing extension: {}", path),
+            ImportError::MissingPath { id } => write!(f, "Missing path for asset {:?}", id),
+            ImportError::InvalidSource { path } => write!(f, "Invalid source: {}", path),
+            ImportError::InvalidExtension { path } => {
+                write!(f, "No importer for extension: {}", path)
+            }
+            ImportError::UnRegistered { id, path } => {
+                write!(f, "Asset type not registered: {:?} ({})", id.ty(), path)
+            }
+            ImportError::MissingMainAsset { path } => write!(f, "Missing main asset: {}", path),
+            ImportError::NoProcessor { path } => write!(f, "No processor: {}", path),
+            ImportError::Import { path, error } => {
+                write!(f, "Failed to import {}: {}", path, error)
+            }
+            ImportError::Process { path, error } => {
+                write!(f, "Failed to process {}: {}", path, error)
+            }
+        }
     }
 }
 
-impl std::error::Error for ImportError {}
+impl std::error::Error for ImportError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            ImportError::Import { error, .. } | ImportError::Process { error, .. } => {
+                Some(error.as_ref())
+            }
+            _ => None,
+        }
+    }
+}
 impl Event for ImportError {}
 
 #[derive(Debug, Clone)]
@@ -255,4 +293,11 @@ impl std::fmt::Display for LoadError {
 }
 
 impl Event for LoadError {}
-impl std::error::Error for LoadError {}
+impl std::error::Error for LoadError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            LoadError::Io { error, .. } => Some(error),
+            _ => None,
+        }
+    }
+}
@@ -176,60 +176,147 @@ impl From<&str> for AssetSourceName {
 }
 
 #[derive(Clone)]
-pub struct AssetSource {
+struct Mount {
     io: Arc<dyn ErasedFileSystem>,
+    priority: i32,
+}
+
+/// A named source made of one or more mounted file systems.
+/// Reads resolve through the mounts in priority order so a path in a higher priority
+/// mount shadows the same path in a lower one. Writes go to the highest priority mount.
+#[derive(Clone)]
+pub struct AssetSource {
+    mounts: Vec<Mount>,
 }
 
 impl AssetSource {
     pub fn new<I: FileSystem>(io: I) -> Self {
-        Self { io: Arc::new(io) }
+        Self {
+            mounts: vec![Mount {
+                io: Arc::new(io),
+                priority: 0,
+            }],
+        }
+    }
+
+    /// Mounts `io` on top of the existing file systems. Mounts with equal priority
+    /// are resolved in the order they were added.
+    pub fn mount<I: FileSystem>(&mut self, io: I, priority: i32) {
+        let index = self
+            .mounts
+            .iter()
+            .position(|mount| mount.priority < priority)
+            .unwrap_or(self.mounts.len());
+
+        let mount = Mount {
+            io: Arc::new(io),
+            priority,
+        };
+
+        self.mounts.insert(index, mount);
     }
 
     pub fn io(&self) -> &dyn ErasedFileSystem {
-        self.io.as_ref()
+        self.mounts[0].io.as_ref()
+    }
+
+    pub fn mounts(&self) -> impl Iterator<Item = &dyn ErasedFileSystem> {
+        self.mounts.iter().map(|mount| mount.io.as_ref())
     }
 
     pub fn reader<'a>(&'a self, path: &'a Path) -> AssetFuture<'a, Box<dyn AssetReader>> {
-        self.io.reader(path)
+        Box::pin(async move {
+            let mut error = AssetIoError::NotFound(path.to_path_buf());
+            for mount in &self.mounts {
+                match mount.io.reader(path).await {
+                    Ok(reader) => return Ok(reader),
+                    Err(AssetIoError::NotFound(_)) => continue,
+                    Err(e) => error = e,
+                }
+            }
+
+            Err(error)
+        })
     }
 
     pub async fn read_dir(
         &self,
         path: impl AsRef<Path>,
     ) -> Result<Box<dyn PathStream>, AssetIoError> {
-        self.io.read_dir(path.as_ref()).await
+        if self.mounts.len() == 1 {
+            return self.io().read_dir(path.as_ref()).await;
+        }
+
+        use futures::StreamExt;
+
+        let mut paths = vec![];
+        let mut error = None;
+        for mount in &self.mounts {
+            match mount.io.read_dir(path.as_ref()).await {
+                Ok(stream) => paths.extend(stream.collect::<Vec<_>>().await),
+                Err(e) => error = Some(e),
+            }
+        }
+
+        match (paths.is_empty(), error) {
+            (true, Some(error)) => Err(error),
+            _ => {
+                paths.sort();
+                paths.dedup();
+                Ok(Box::new(futures::stream::iter(paths)))
+            }
+        }
     }
 
     pub fn is_dir<'a>(&'a self, path: &'a Path) -> AssetFuture<bool> {
-        self.io.is_dir(path)
+        Box::pin(async move {
+            let mut error = AssetIoError::NotFound(path.to_path_buf());
+            for mount in &self.mounts {
+                match mount.io.is_dir(path).await {
+                    Ok(value) => return Ok(value),
+                    Err(AssetIoError::NotFound(_)) => continue,
+                    Err(e) => error = e,
+                }
+            }
+
+            Err(error)
+        })
     }
 
     pub fn writer<'a>(&'a self, path: &'a Path) -> AssetFuture<Box<dyn AssetWriter>> {
-        self.io.writer(path)
+        self.io().writer(path)
     }
 
     pub fn rename<'a>(&'a self, from: &'a Path, to: &'a Path) -> AssetFuture<()> {
-        self.io.rename(from, to)
+        self.io().rename(from, to)
     }
 
     pub fn create_dir<'a>(&'a self, path: &'a Path) -> AssetFuture<()> {
-        self.io.create_dir(path)
+        self.io().create_dir(path)
     }
 
     pub fn create_dir_all<'a>(&'a self, path: &'a Path) -> AssetFuture<()> {
-        self.io.create_dir_all(path)
+        self.io().create_dir_all(path)
     }
 
     pub fn remove<'a>(&'a self, path: &'a Path) -> AssetFuture<()> {
-        self.io.remove(path)
+        self.io().remove(path)
     }
 
     pub fn remove_dir<'a>(&'a self, path: &'a Path) -> AssetFuture<()> {
-        self.io.remove_dir(path)
+        self.io().remove_dir(path)
     }
 
     pub fn exists<'a>(&'a self, path: &'a Path) -> AssetFuture<bool> {
-        self.io.exists(path)
+        Box::pin(async move {
+            for mount in &self.mounts {
+                if mount.io.exists(path).await? {
+                    return Ok(true);
+                }
+            }
+
+            Ok(false)
+        })
     }
 
     pub fn metadata_path(path: &Path) -> PathBuf {
@@ -295,6 +382,18 @@ impl AssetSources {
         self.sources.insert(name, AssetSource::new(io));
     }
 
+    /// Mounts `io` onto the source `name`, creating the source if it doesn't exist.
+    pub fn mount<I: FileSystem>(&mut self, name: AssetSourceName, io: I, priority: i32) {
+        match self.sources.get_mut(&name) {
+            Some(source) => source.mount(io, priority),
+            None => {
+                let mut source = AssetSource::new(io);
+                source.mounts[0].priority = priority;
+                self.sources.insert(name, source);
+            }
+        }
+    }
+
     pub fn get(&self, name: &AssetSourceName) -> Option<&AssetSource> {
         self.sources.get(name)
     }
@@ -307,3 +406,47 @@ impl AssetSources {
         self.sources.iter()
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::{AssetSourceName, AssetSources};
+    use crate::io::{vfs::VirtualFs, FileSystem};
+    use futures_lite::{future::block_on, AsyncReadExt, AsyncWriteExt};
+    use std::path::Path;
+
+    fn write_text(fs: &VirtualFs, path: &str, text: &str) {
+        block_on(async {
+            let mut writer = fs.writer(Path::new(path)).await.unwrap();
+            writer.write_all(text.as_bytes()).await.unwrap();
+            writer.flush().await.unwrap();
+        });
+    }
+
+    #[test]
+    fn mount_priority() {
+        let base = VirtualFs::new("");
+        let overlay = VirtualFs::new("");
+        write_text(&base, "config.txt", "base");
+        write_text(&base, "base.txt", "base");
+        write_text(&overlay, "config.txt", "overlay");
+
+        let mut sources = AssetSources::new();
+        sources.mount(AssetSourceName::Default, base, 0);
+        sources.mount(AssetSourceName::Default, overlay, 1);
+
+        let source = sources.get(&AssetSourceName::Default).unwrap();
+        let read = |path: &str| {
+            block_on(async {
+                let mut reader = source.reader(Path::new(path)).await.unwrap();
+                let mut text = String::new();
+                reader.read_to_string(&mut text).await.unwrap();
+                text
+            })
+        };
+
+        assert_eq!(read("config.txt"), "overlay");
+        assert_eq!(read("base.txt"), "base");
+        assert!(block_on(source.exists(Path::new("base.txt"))).unwrap());
+        assert!(!block_on(source.exists(Path::new("missing.txt"))).unwrap());
+    }
+}
@@ -436,7 +436,8 @@ impl FileSystem for VirtualFs {
     }
 
     async fn exists(&self, path: &std::path::Path) -> Result<bool, super::AssetIoError> {
+        let path = path.with_prefix(&self.root);
         let fs = self.fs.read().await;
-        Ok(fs.entries.contains_key(path))
+        Ok(fs.entries.contains_key(path.as_ref()))
     }
 }
@@ -1,8 +1,10 @@
 pub mod asset;
 pub mod database;
+pub mod handle;
 pub mod importer;
 pub mod io;
 pub mod plugin;
+pub mod server;
 
 pub use futures_lite::*;
 pub use uuid::*;
@@ -2,16 +2,22 @@ use crate::{
     asset::{Asset, AssetType, Assets},
     database::{
         config::AssetConfig,
-        events::{on_asset_event, on_assets_unloaded, AssetEvent, NotifyDepsUnloaded},
+        events::{
+            on_asset_event, on_assets_unloaded, AssetEvent, NotifyDepsUnloaded, UnloadAssets,
+        },
         update::RefreshMode,
         AssetDatabase, DatabaseInitError,
     },
     importer::{ImportError, Importer, LoadError, Processor},
     io::{embedded::EmbeddedFs, local::LocalFs, source::AssetSourceName, FileSystem},
 };
-use ecs::{core::resource::ResMut, event::Events};
+use ecs::{
+    core::resource::{Res, ResMut},
+    event::Events,
+    world::action::WorldActions,
+};
 use futures::executor::block_on;
-use game::{GameBuilder, Init, Plugin};
+use game::{GameBuilder, Init, Last, Plugin};
 use std::path::PathBuf;
 
 pub struct AssetPlugin;
@@ -29,6 +35,7 @@ impl Plugin for AssetPlugin {
         game.register_event::<NotifyDepsUnloaded>();
         game.observe::<NotifyDepsUnloaded, _>(on_assets_unloaded);
         game.add_systems(Init, init_asset_database);
+        game.add_systems(Last, unload_released_assets);
     }
 
     fn finish(&mut self, game: &mut GameBuilder) {
@@ -123,6 +130,14 @@ fn init_asset_database(
     block_on(init(&mut database, &mut events));
 }
 
+fn unload_released_assets(database: Res<AssetDatabase>, actions: &WorldActions) {
+    let delay = database.config().unload_delay();
+    let ids = database.refs().take_released(delay);
+    if !ids.is_empty() {
+        actions.add(UnloadAssets::new(ids));
+    }
+}
+
 async fn init(database: &mut AssetDatabase, events: &mut Events<DatabaseInitError>) {
     for (_, source) in database.config().sources().iter() {
         let _ = source.create_dir(&PathBuf::new()).await;
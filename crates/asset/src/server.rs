@@ -0,0 +1,68 @@
+use crate::{
+    asset::{Asset, Assets},
+    database::{state::LoadState, AssetDatabase},
+    handle::Handle,
+    importer::LoadError,
+    io::source::AssetPath,
+};
+use ecs::{
+    system::SystemArg,
+    world::{cell::WorldCell, World},
+};
+
+/// Loads assets by path and returns handles to them.
+/// Assets load in the background and can be fetched once they're loaded.
+pub struct AssetServer<'a> {
+    world: &'a World,
+}
+
+impl<'a> AssetServer<'a> {
+    pub fn new(world: &'a World) -> Self {
+        Self { world }
+    }
+
+    pub fn database(&self) -> &'a AssetDatabase {
+        self.world.resource::<AssetDatabase>()
+    }
+
+    /// Starts loading the asset at `path` and returns a strong handle to it.
+    /// Fails if the path hasn't been imported yet.
+    pub fn load<A: Asset>(&self, path: impl Into<AssetPath>) -> Result<Handle<A>, LoadError> {
+        let database = self.database();
+        let path: AssetPath = path.into();
+        let id = match database.library().read_blocking().get_id(&path) {
+            Some(id) => id,
+            None => return Err(LoadError::NotFound { path }),
+        };
+
+        let handle = database.handle::<A>(id);
+        let state = database.states().read_blocking().load_state(id);
+        if !state.is_loaded() && !state.is_loading() {
+            database.load([id]);
+        }
+
+        Ok(handle)
+    }
+
+    pub fn load_state<A: Asset>(&self, handle: &Handle<A>) -> LoadState {
+        let states = self.database().states().read_blocking();
+        states.load_state(handle.id())
+    }
+
+    pub fn get<A: Asset>(&self, handle: &Handle<A>) -> Option<&'a A> {
+        let assets = self.world.try_resource::<Assets<A>>()?;
+        assets.get(&handle.id())
+    }
+}
+
+impl SystemArg for AssetServer<'_> {
+    type Item<'a> = AssetServer<'a>;
+
+    fn get<'a>(world: &'a WorldCell) -> Self::Item<'a> {
+        AssetServer::new(world.get())
+    }
+
+    fn is_send() -> bool {
+        false
+    }
+}
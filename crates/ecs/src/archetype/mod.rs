@@ -1,9 +1,12 @@
-use crate::core::{
-    bitset::Bitset,
-    component::{Component, ComponentId},
-    entity::Entity,
+use crate::{
+    core::{
+        bitset::Bitset,
+        component::{Component, ComponentId},
+        entity::Entity,
+    },
+    storage::{SparseSet, StorageKind},
 };
-use hashbrown::HashSet;
+use hashbrown::{HashMap, HashSet};
 use indexmap::{IndexMap, IndexSet};
 use std::hash::Hash;
 use table::{ColumnCell, Row, Table};
@@ -13,6 +16,7 @@ pub struct Archetypes {
     entities: IndexMap<Entity, ArchetypeId>,
     archetypes: IndexMap<ArchetypeId, Archetype>,
     components: IndexSet<ComponentId>,
+    sparse: HashMap<ComponentId, SparseSet>,
     root: ArchetypeId,
 }
 
@@ -27,6 +31,7 @@ impl Archetypes {
             entities: IndexMap::new(),
             archetypes,
             components: IndexSet::new(),
+            sparse: HashMap::new(),
             root,
         }
     }
@@ -35,6 +40,14 @@ impl Archetypes {
         self.root
     }
 
+    pub fn len(&self) -> usize {
+        self.archetypes.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.archetypes.is_empty()
+    }
+
     pub fn entity_archetype(&self, entity: Entity) -> Option<&Archetype> {
         self.entities
             .get(&entity)
@@ -47,22 +60,43 @@ impl Archetypes {
             .and_then(|id| self.archetypes.get_mut(id))
     }
 
+    /// Iterates every archetype, including the empty root, with its entity count.
+    pub fn iter(&self) -> impl Iterator<Item = (&Archetype, usize)> {
+        self.archetypes
+            .values()
+            .map(|archetype| (archetype, archetype.len()))
+    }
+
     pub fn archetype(&self, id: ArchetypeId) -> Option<&Archetype> {
         self.archetypes.get(&id)
     }
 
     pub fn get_component<C: Component>(&self, entity: Entity) -> Option<&C> {
+        if let Some(set) = self.sparse.get(&ComponentId::of::<C>()) {
+            return set.get(&entity);
+        }
+
         let archetype = self.entity_archetype(entity)?;
         archetype.table.get_component(&entity)
     }
 
     pub fn get_component_mut<C: Component>(&mut self, entity: Entity) -> Option<&mut C> {
+        let id = ComponentId::of::<C>();
+        if self.sparse.contains_key(&id) {
+            return self.sparse.get_mut(&id)?.get_mut(&entity);
+        }
+
         let archetype = self.entity_archetype_mut(entity)?;
         archetype.table.get_component_mut(&entity)
     }
 
     pub fn has_component<C: Component>(&self, entity: Entity) -> bool {
-        let index = self.component_index(&ComponentId::of::<C>());
+        let id = ComponentId::of::<C>();
+        if let Some(set) = self.sparse.get(&id) {
+            return set.contains(&entity);
+        }
+
+        let index = self.component_index(&id);
         match self.entity_archetype(entity) {
             Some(archetype) => archetype.has_component(index),
             None => return false,
@@ -75,13 +109,25 @@ impl Archetypes {
         components: impl IntoIterator<Item = impl AsRef<ComponentId>>,
     ) -> bool {
         match self.entity_archetype(entity) {
-            Some(archetype) => components
-                .into_iter()
-                .all(|c| archetype.has_component_id(c.as_ref())),
+            Some(archetype) => components.into_iter().all(|c| {
+                let id = c.as_ref();
+                match self.sparse.get(id) {
+                    Some(set) => set.contains(&entity),
+                    None => archetype.has_component_id(id),
+                }
+            }),
             None => false,
         }
     }
 
+    pub fn is_sparse(&self, component: &ComponentId) -> bool {
+        self.sparse.contains_key(component)
+    }
+
+    pub fn sparse_set(&self, component: &ComponentId) -> Option<&SparseSet> {
+        self.sparse.get(component)
+    }
+
     pub fn component_index(&self, component: &ComponentId) -> usize {
         self.components
             .get_index_of(component)
@@ -89,8 +135,15 @@ impl Archetypes {
     }
 
     pub fn register_component<C: Component>(&mut self) {
+        self.register_component_with::<C>(StorageKind::Table);
+    }
+
+    pub fn register_component_with<C: Component>(&mut self, storage: StorageKind) {
         let id = ComponentId::of::<C>();
         self.components.insert(id);
+        if storage == StorageKind::SparseSet {
+            self.sparse.entry(id).or_insert_with(SparseSet::new::<C>);
+        }
     }
 
     pub fn add_entity(&mut self, entity: Entity) {
@@ -126,16 +179,38 @@ impl Archetypes {
         Some((id, components))
     }
 
+    pub fn entities(&self) -> impl Iterator<Item = &Entity> {
+        self.entities.keys()
+    }
+
+    /// Removes the entity along with its sparse set components.
+    pub fn despawn(&mut self, entity: Entity) -> Option<Row> {
+        let (_, mut row) = self.remove_entity(entity)?;
+        for (id, set) in self.sparse.iter_mut() {
+            if let Some(component) = set.remove(&entity) {
+                row.add_cell(*id, component);
+            }
+        }
+
+        Some(row)
+    }
+
     pub fn add_component<C: Component>(
         &mut self,
         entity: Entity,
         component: C,
     ) -> Option<EntityMove> {
+        let id = ComponentId::of::<C>();
+        if self.is_sparse(&id) {
+            let mut row = Row::new();
+            row.add_cell(id, ColumnCell::from(component));
+            return self.add_components(entity, row);
+        }
+
         let (archetype, mut components) = match self.remove_entity(entity) {
             Some(data) => data,
             None => (self.root, Row::new()),
         };
-        let id = ComponentId::of::<C>();
         let mut replaced = Row::new();
         let mut added = HashSet::new();
 
@@ -155,6 +230,14 @@ impl Archetypes {
     }
 
     pub fn add_components(&mut self, entity: Entity, mut components: Row) -> Option<EntityMove> {
+        let sparse = self.take_sparse(&mut components);
+        if components.is_empty() && !sparse.is_empty() {
+            let archetype = self.archetype_id(entity);
+            let mut mv = EntityMove::new_added(archetype, archetype, HashSet::new(), Row::new());
+            self.add_sparse(entity, sparse, &mut mv);
+            return Some(mv);
+        }
+
         let (archetype, mut row) = match self.remove_entity(entity) {
             Some(data) => data,
             None => (self.root, Row::new()),
@@ -173,18 +256,27 @@ impl Archetypes {
         row.sort();
 
         let to = ArchetypeId::from_iter(row.ids());
-        let mv = EntityMove::new_added(archetype, to, added, replaced);
+        let mut mv = EntityMove::new_added(archetype, to, added, replaced);
         self.add_entity_sorted(entity, to, row);
+        self.add_sparse(entity, sparse, &mut mv);
 
         Some(mv)
     }
 
     pub fn remove_component<C: Component>(&mut self, entity: Entity) -> Option<EntityMove> {
+        let id = ComponentId::of::<C>();
+        if let Some(set) = self.sparse.get_mut(&id) {
+            let component = set.remove(&entity)?;
+            let archetype = self.archetype_id(entity);
+            let mut removed = Row::new();
+            removed.add_cell(id, component);
+            return Some(EntityMove::new_removed(archetype, archetype, removed));
+        }
+
         let (archetype, mut row) = match self.remove_entity(entity) {
             Some(data) => data,
             None => (self.root, Row::new()),
         };
-        let id = ComponentId::of::<C>();
         let component = row.remove_cell(&id)?;
         let mut removed = Row::new();
         removed.add_cell(id, component);
@@ -201,16 +293,33 @@ impl Archetypes {
         entity: Entity,
         components: impl IntoIterator<Item = impl AsRef<ComponentId>>,
     ) -> Option<EntityMove> {
+        let mut removed = Row::new();
+        let mut table = vec![];
+        for component in components {
+            let id = *component.as_ref();
+            match self.sparse.get_mut(&id) {
+                Some(set) => {
+                    if let Some(component) = set.remove(&entity) {
+                        removed.add_cell(id, component);
+                    }
+                }
+                None => table.push(id),
+            }
+        }
+
+        if table.is_empty() {
+            let archetype = self.archetype_id(entity);
+            return Some(EntityMove::new_removed(archetype, archetype, removed));
+        }
+
         let (archetype, mut row) = match self.remove_entity(entity) {
             Some(data) => data,
             None => (self.root, Row::new()),
         };
-        let mut removed = Row::new();
 
-        for component in components {
-            let id = component.as_ref();
-            if let Some(component) = row.remove_cell(id) {
-                removed.add_cell(*id, component);
+        for id in table {
+            if let Some(component) = row.remove_cell(&id) {
+                removed.add_cell(id, component);
             }
         }
 
@@ -221,9 +330,103 @@ impl Archetypes {
         Some(mv)
     }
 
+    /// Adds and removes components in a single move between archetypes.
+    pub fn modify_components(
+        &mut self,
+        entity: Entity,
+        mut components: Row,
+        remove: impl IntoIterator<Item = impl AsRef<ComponentId>>,
+    ) -> Option<EntityMove> {
+        let sparse = self.take_sparse(&mut components);
+        let mut removed = Row::new();
+        let mut table = vec![];
+        for component in remove {
+            let id = *component.as_ref();
+            match self.sparse.get_mut(&id) {
+                Some(set) => {
+                    if let Some(component) = set.remove(&entity) {
+                        removed.add_cell(id, component);
+                    }
+                }
+                None => table.push(id),
+            }
+        }
+
+        let (archetype, mut row) = match self.remove_entity(entity) {
+            Some(data) => data,
+            None => (self.root, Row::new()),
+        };
+
+        for id in table {
+            if let Some(component) = row.remove_cell(&id) {
+                removed.add_cell(id, component);
+            }
+        }
+
+        let mut replaced = Row::new();
+        let mut added = HashSet::new();
+        for (id, component) in components.drain() {
+            if let Some(component) = row.add_cell(id, component) {
+                replaced.add_cell(id, component);
+            } else {
+                added.insert(id);
+            }
+        }
+
+        row.sort();
+
+        let to = ArchetypeId::from_iter(row.ids());
+        let mut mv = EntityMove::new(archetype, to, added, removed, replaced);
+        self.add_entity_sorted(entity, to, row);
+        self.add_sparse(entity, sparse, &mut mv);
+
+        Some(mv)
+    }
+
     pub fn clear(&mut self) {
         self.entities.clear();
         self.archetypes.clear();
+        for set in self.sparse.values_mut() {
+            let entities = set.entities().to_vec();
+            for entity in entities {
+                set.remove(&entity);
+            }
+        }
+    }
+
+    fn archetype_id(&mut self, entity: Entity) -> ArchetypeId {
+        match self.entities.get(&entity) {
+            Some(id) => *id,
+            None => {
+                self.add_entity(entity);
+                self.root
+            }
+        }
+    }
+
+    fn take_sparse(&self, components: &mut Row) -> Row {
+        let mut sparse = Row::new();
+        for id in self.sparse.keys() {
+            if let Some(component) = components.remove_cell(id) {
+                sparse.add_cell(*id, component);
+            }
+        }
+
+        sparse
+    }
+
+    fn add_sparse(&mut self, entity: Entity, mut components: Row, mv: &mut EntityMove) {
+        for (id, component) in components.drain() {
+            let set = self.sparse.get_mut(&id).unwrap();
+            match set.insert(entity, component) {
+                Some(component) => {
+                    mv.replaced.add_cell(id, component);
+                }
+                None => {
+                    mv.added.insert(id);
+                }
+            }
+        }
     }
 
     #[inline]
@@ -310,6 +513,19 @@ impl Archetype {
     pub fn bits(&self) -> &Bitset {
         &self.bits
     }
+
+    pub fn component_ids(&self) -> impl Iterator<Item = &ComponentId> {
+        self.table.ids()
+    }
+
+    /// The number of entities in this archetype.
+    pub fn len(&self) -> usize {
+        self.table.entities().len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.table.entities().is_empty()
+    }
 }
 
 impl Hash for Archetype {
@@ -376,3 +592,57 @@ impl EntityMove {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::Archetypes;
+    use crate::core::{
+        component::{Component, ComponentId},
+        entity::Entity,
+    };
+
+    struct Position;
+    impl Component for Position {}
+
+    struct Velocity;
+    impl Component for Velocity {}
+
+    #[test]
+    fn archetype_entity_counts() {
+        let mut archetypes = Archetypes::new();
+        archetypes.register_component::<Position>();
+        archetypes.register_component::<Velocity>();
+
+        for id in 0..3 {
+            let entity = Entity::new(id);
+            archetypes.add_entity(entity);
+            archetypes.add_component(entity, Position);
+        }
+
+        for id in 3..5 {
+            let entity = Entity::new(id);
+            archetypes.add_entity(entity);
+            archetypes.add_component(entity, Position);
+            archetypes.add_component(entity, Velocity);
+        }
+
+        let counts = archetypes
+            .iter()
+            .map(|(archetype, count)| {
+                let mut ids = archetype.component_ids().copied().collect::<Vec<_>>();
+                ids.sort();
+                (ids, count)
+            })
+            .collect::<Vec<_>>();
+
+        let position = ComponentId::of::<Position>();
+        let velocity = ComponentId::of::<Velocity>();
+        let mut both = vec![position, velocity];
+        both.sort();
+
+        assert!(counts.contains(&(vec![], 0)));
+        assert!(counts.contains(&(vec![position], 3)));
+        assert!(counts.contains(&(both, 2)));
+        assert_eq!(counts.iter().map(|(_, count)| count).sum::<usize>(), 5);
+    }
+}
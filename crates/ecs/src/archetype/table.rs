@@ -147,11 +147,11 @@ impl Column {
     }
 
     pub fn push_cell(&mut self, cell: ColumnCell) {
-        self.data.extend(cell.data.into())
+        self.data.extend(Blob::from(cell.data))
     }
 
     pub fn insert_cell(&mut self, index: usize, cell: ColumnCell) {
-        self.data.insert_blob(index, cell.data.into())
+        self.data.insert_blob(index, Blob::from(cell.data))
     }
 
     pub fn remove_cell(&mut self, index: usize) -> ColumnCell {
@@ -502,7 +502,7 @@ impl Table {
 
     pub fn remove_entity(&mut self, entity: &Entity) -> Option<Row> {
         let index = self.rows.get_index_of(entity)?;
-        self.rows.swap_remove_index(index);
+        self.rows.shift_remove_index(index);
         let mut row = Row::new();
         for (id, column) in self.components.iter_mut() {
             let cell = column.remove_cell(index);
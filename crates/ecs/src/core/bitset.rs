@@ -59,13 +59,44 @@ impl Bitset {
         self.bits.clear();
     }
 
+    /// Returns the bits set in either bitset.
+    pub fn union(&self, other: &Self) -> Self {
+        let len = self.bits.len().max(other.bits.len());
+        let bits = (0..len).map(|i| self.word(i) | other.word(i)).collect();
+        Self { bits }
+    }
+
+    /// Returns the bits set in both bitsets.
+    pub fn intersection(&self, other: &Self) -> Self {
+        let len = self.bits.len().min(other.bits.len());
+        let bits = (0..len).map(|i| self.word(i) & other.word(i)).collect();
+        Self { bits }
+    }
+
+    /// Returns the bits set in this bitset but not in `other`.
+    pub fn difference(&self, other: &Self) -> Self {
+        let bits = (0..self.bits.len())
+            .map(|i| self.word(i) & !other.word(i))
+            .collect();
+        Self { bits }
+    }
+
     pub fn iter(&self) -> BitsetIter {
+        self.iter_ones()
+    }
+
+    /// Iterates the indices of the set bits in ascending order.
+    pub fn iter_ones(&self) -> BitsetIter<'_> {
         BitsetIter {
             bits: self,
             word: 0,
-            bit: 0,
+            current: self.word(0),
         }
     }
+
+    fn word(&self, index: usize) -> u64 {
+        self.bits.get(index).copied().unwrap_or(0)
+    }
 }
 
 impl Default for Bitset {
@@ -77,26 +108,82 @@ impl Default for Bitset {
 pub struct BitsetIter<'a> {
     bits: &'a Bitset,
     word: usize,
-    bit: usize,
+    current: u64,
 }
 
 impl<'a> Iterator for BitsetIter<'a> {
     type Item = usize;
 
     fn next(&mut self) -> Option<Self::Item> {
-        while self.word < self.bits.bits.len() {
-            let word = self.bits.bits[self.word];
-            while self.bit < 64 {
-                if (word & (1 << self.bit)) != 0 {
-                    let index = self.word * 64 + self.bit;
-                    self.bit += 1;
-                    return Some(index);
-                }
-                self.bit += 1;
-            }
+        while self.current == 0 {
             self.word += 1;
-            self.bit = 0;
+            if self.word >= self.bits.bits.len() {
+                return None;
+            }
+            self.current = self.bits.bits[self.word];
         }
-        None
+
+        let bit = self.current.trailing_zeros() as usize;
+        self.current &= self.current - 1;
+        Some(self.word * 64 + bit)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::Bitset;
+
+    fn bitset(indices: &[usize]) -> Bitset {
+        let mut bits = Bitset::new();
+        for index in indices {
+            bits.set(*index);
+        }
+        bits
+    }
+
+    #[test]
+    fn bitset_union() {
+        let a = bitset(&[1, 3]);
+        let b = bitset(&[3, 70, 130]);
+
+        let union = a.union(&b);
+        assert_eq!(union.iter_ones().collect::<Vec<_>>(), vec![1, 3, 70, 130]);
+        assert_eq!(
+            b.union(&a).iter_ones().collect::<Vec<_>>(),
+            vec![1, 3, 70, 130]
+        );
+    }
+
+    #[test]
+    fn bitset_intersection() {
+        let a = bitset(&[1, 3, 70]);
+        let b = bitset(&[3, 70, 130]);
+
+        let intersection = a.intersection(&b);
+        assert_eq!(intersection.iter_ones().collect::<Vec<_>>(), vec![3, 70]);
+        assert_eq!(
+            b.intersection(&a).iter_ones().collect::<Vec<_>>(),
+            vec![3, 70]
+        );
+    }
+
+    #[test]
+    fn bitset_difference() {
+        let a = bitset(&[1, 3, 70, 130]);
+        let b = bitset(&[3, 70]);
+
+        assert_eq!(
+            a.difference(&b).iter_ones().collect::<Vec<_>>(),
+            vec![1, 130]
+        );
+        assert_eq!(b.difference(&a).iter_ones().count(), 0);
+    }
+
+    #[test]
+    fn bitset_iter_ones() {
+        let bits = bitset(&[0, 63, 64, 500]);
+        assert_eq!(bits.iter_ones().collect::<Vec<_>>(), vec![0, 63, 64, 500]);
+        assert_eq!(Bitset::with_capacity(256).iter_ones().count(), 0);
+        assert_eq!(Bitset::new().iter_ones().count(), 0);
     }
 }
@@ -9,6 +9,11 @@ impl ComponentId {
         Self(Type::of::<C>())
     }
 }
+impl AsRef<ComponentId> for ComponentId {
+    fn as_ref(&self) -> &ComponentId {
+        self
+    }
+}
 impl std::ops::Deref for ComponentId {
     type Target = Type;
     fn deref(&self) -> &Self::Target {
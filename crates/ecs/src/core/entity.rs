@@ -22,6 +22,8 @@ impl Entity {
     }
 }
 
+/// Allocates entity ids. Ids are only stable within a single run unless
+/// the allocator is [`reset`](Entities::reset) before spawning.
 #[derive(Debug)]
 pub struct Entities {
     current: u32,
@@ -64,7 +66,58 @@ impl Entities {
         false
     }
 
+    pub fn len(&self) -> usize {
+        self.generations.len() - self.free.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Clears recycled ids and restarts allocation from index 0.
+    /// Panics if any entities are still alive.
+    pub fn reset(&mut self) {
+        assert!(
+            self.is_empty(),
+            "Cannot reset entities while {} entities are alive",
+            self.len()
+        );
+
+        self.current = 0;
+        self.generations.clear();
+        self.free.clear();
+    }
+
     pub fn iter(&self) -> impl Iterator<Item = (&u32, &u32)> + '_ {
         self.generations.iter().map(|(id, gen)| (id, gen))
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::{Entities, Entity};
+
+    #[test]
+    fn reset_entities() {
+        let mut entities = Entities::new();
+        let first = entities.spawn();
+        let second = entities.spawn();
+        entities.despawn(&first);
+        entities.despawn(&second);
+
+        let recycled = entities.spawn();
+        assert_eq!(recycled.generation, 1);
+        entities.despawn(&recycled);
+
+        entities.reset();
+        assert_eq!(entities.spawn(), Entity::ZERO);
+    }
+
+    #[test]
+    #[should_panic]
+    fn reset_live_entities() {
+        let mut entities = Entities::new();
+        entities.spawn();
+        entities.reset();
+    }
+}
@@ -300,12 +300,16 @@ impl Blob {
 
         let start = (self.length - 1) * self.aligned_layout.size();
         let end = start + self.aligned_layout.size();
-        let data = self.data.drain(start..end).collect::<Vec<_>>();
-
-        let start = index * self.aligned_layout.size();
-        let end = start + self.aligned_layout().size();
-
-        let data = self.data.splice(start..end, data).collect::<Vec<_>>();
+        let last = self.data.drain(start..end).collect::<Vec<_>>();
+
+        let data = match index == self.length - 1 {
+            true => last,
+            false => {
+                let start = index * self.aligned_layout.size();
+                let end = start + self.aligned_layout().size();
+                self.data.splice(start..end, last).collect::<Vec<_>>()
+            }
+        };
 
         self.length -= 1;
         unsafe {
@@ -1,6 +1,10 @@
-use super::{internal::blob::BlobCell, Type};
+use super::{internal::blob::BlobCell, IndexMap, Type};
 use hashbrown::HashMap;
-use std::{hash::Hash, thread::ThreadId};
+use std::{
+    hash::Hash,
+    sync::atomic::{AtomicU64, Ordering},
+    thread::ThreadId,
+};
 
 pub trait Resource: 'static {}
 
@@ -63,6 +67,7 @@ impl ResourceMeta {
 pub(crate) struct ResourceInfo {
     data: Option<BlobCell>,
     meta: ResourceMeta,
+    version: AtomicU64,
 }
 
 impl ResourceInfo {
@@ -70,14 +75,26 @@ impl ResourceInfo {
         let data = Some(BlobCell::new(resource));
         let meta = ResourceMeta::new::<R>(true);
 
-        Self { data, meta }
+        Self {
+            data,
+            meta,
+            version: AtomicU64::new(1),
+        }
     }
 
     pub fn new_non_send_sync<R: Resource>(resource: R) -> Self {
         let data = Some(BlobCell::new(resource));
         let meta = ResourceMeta::new::<R>(false);
 
-        Self { data, meta }
+        Self {
+            data,
+            meta,
+            version: AtomicU64::new(1),
+        }
+    }
+
+    pub fn version(&self) -> u64 {
+        self.version.load(Ordering::Relaxed)
     }
 
     pub fn as_ref<R: Resource>(&self) -> &R {
@@ -116,14 +133,18 @@ impl Drop for ResourceInfo {
     }
 }
 
+/// Type erased resources, kept in insertion order.
 pub struct Resources<const SEND: bool> {
-    resources: HashMap<ResourceId, ResourceInfo>,
+    resources: IndexMap<ResourceId, ResourceInfo>,
+    /// Versions of removed resources, so adding them back still counts as a change.
+    removed: HashMap<ResourceId, u64>,
 }
 
 impl<const SEND: bool> Resources<SEND> {
     pub fn new() -> Self {
         Self {
-            resources: HashMap::new(),
+            resources: IndexMap::new(),
+            removed: HashMap::new(),
         }
     }
 
@@ -135,6 +156,15 @@ impl<const SEND: bool> Resources<SEND> {
             ResourceInfo::new_non_send_sync(resource)
         };
 
+        // Replacing or re-adding a resource counts as changing it.
+        let old = match self.resources.get(&id) {
+            Some(old) => Some(old.version()),
+            None => self.removed.remove(&id),
+        };
+        if let Some(old) = old {
+            info.version.store(old + 1, Ordering::Relaxed);
+        }
+
         self.resources.insert(id, info);
     }
 
@@ -157,29 +187,91 @@ impl<const SEND: bool> Resources<SEND> {
         self.resources.get(&id).map(|info| info.as_ref())
     }
 
+    /// Gets a resource mutably, marking it as changed.
     pub fn try_get_mut<R: Resource>(&mut self) -> Option<&mut R> {
         let id = ResourceId::of::<R>();
-        self.resources.get_mut(&id).map(|info| info.as_mut())
+        self.resources.get_mut(&id).map(|info| {
+            info.version.fetch_add(1, Ordering::Relaxed);
+            info.as_mut()
+        })
+    }
+
+    /// The resource's version, which is bumped every time it's accessed mutably.
+    pub fn version<R: Resource>(&self) -> Option<u64> {
+        let id = ResourceId::of::<R>();
+        self.resources.get(&id).map(|info| info.version())
+    }
+
+    /// Gets a resource mutably without marking it as changed.
+    /// The returned `ResMut` marks it when dereferenced mutably.
+    pub fn try_get_tracked<R: Resource>(&mut self, changed: bool) -> Option<ResMut<'_, R>> {
+        let id = ResourceId::of::<R>();
+        let ResourceInfo {
+            data,
+            meta,
+            version,
+        } = self.resources.get_mut(&id)?;
+        let data = data
+            .as_mut()
+            .unwrap_or_else(|| panic!("Resource {} no longer exists", meta.name));
+
+        Some(ResMut {
+            ptr: data.value_mut::<R>(),
+            version: Some(version),
+            changed,
+        })
     }
 
     pub fn remove<R: Resource>(&mut self) -> Option<R> {
         let id = ResourceId::of::<R>();
-        self.resources.get_mut(&id).map(|info| info.take())
+        let mut info = self.resources.shift_remove(&id)?;
+        self.removed.insert(id, info.version());
+        Some(info.take())
     }
 
     pub fn contains<R: Resource>(&self) -> bool {
-        let id = ResourceId::of::<R>();
-        self.resources.contains_key(&id)
+        self.contains_type(Type::of::<R>())
+    }
+
+    pub fn contains_type(&self, ty: Type) -> bool {
+        self.resources.contains_key(&ResourceId::dynamic(ty))
+    }
+
+    /// Iterates the type and name of each resource in insertion order.
+    pub fn iter_metadata(&self) -> impl Iterator<Item = (Type, &'static str)> + '_ {
+        self.resources
+            .iter()
+            .map(|(id, info)| (id.0, info.meta.name()))
+    }
+
+    pub fn len(&self) -> usize {
+        self.resources.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.resources.is_empty()
     }
 }
 
+/// Shared access to a resource.
 pub struct Res<'a, R: Resource> {
     ptr: &'a R,
+    changed: bool,
 }
 
 impl<'a, R: Resource> Res<'a, R> {
     pub fn new(ptr: &'a R) -> Self {
-        Self { ptr }
+        Self { ptr, changed: true }
+    }
+
+    pub fn with_changed(ptr: &'a R, changed: bool) -> Self {
+        Self { ptr, changed }
+    }
+
+    /// Returns true if the resource was accessed mutably since the system last ran.
+    /// Always true outside of a system.
+    pub fn is_changed(&self) -> bool {
+        self.changed
     }
 }
 
@@ -191,13 +283,26 @@ impl<'a, R: Resource> std::ops::Deref for Res<'a, R> {
     }
 }
 
+/// Mutable access to a resource. Dereferencing it mutably marks the resource as changed.
 pub struct ResMut<'a, R: Resource> {
     ptr: &'a mut R,
+    version: Option<&'a AtomicU64>,
+    changed: bool,
 }
 
 impl<'a, R: Resource> ResMut<'a, R> {
     pub fn new(ptr: &'a mut R) -> Self {
-        Self { ptr }
+        Self {
+            ptr,
+            version: None,
+            changed: true,
+        }
+    }
+
+    /// Returns true if the resource was accessed mutably since the system last ran.
+    /// Always true outside of a system.
+    pub fn is_changed(&self) -> bool {
+        self.changed
     }
 }
 
@@ -211,6 +316,44 @@ impl<'a, R: Resource> std::ops::Deref for ResMut<'a, R> {
 
 impl<'a, R: Resource> std::ops::DerefMut for ResMut<'a, R> {
     fn deref_mut(&mut self) -> &mut Self::Target {
+        if let Some(version) = self.version {
+            version.fetch_add(1, Ordering::Relaxed);
+        }
+
         &mut self.ptr
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::{Resource, Resources};
+    use crate::core::Type;
+
+    struct Gravity;
+    impl Resource for Gravity {}
+
+    struct Score;
+    impl Resource for Score {}
+
+    struct Settings;
+    impl Resource for Settings {}
+
+    #[test]
+    fn resource_metadata_order() {
+        let mut resources = Resources::<true>::new();
+        resources.add(Score);
+        resources.add(Gravity);
+        resources.add(Settings);
+
+        let names = resources
+            .iter_metadata()
+            .map(|(_, name)| name.rsplit("::").next().unwrap())
+            .collect::<Vec<_>>();
+        assert_eq!(names, vec!["Score", "Gravity", "Settings"]);
+        assert!(resources.contains_type(Type::of::<Gravity>()));
+
+        resources.remove::<Gravity>();
+        assert!(!resources.contains_type(Type::of::<Gravity>()));
+        assert_eq!(resources.len(), 2);
+    }
+}
@@ -1,6 +1,14 @@
 use crate::{
-    core::{resource::Resource, Type},
-    system::schedule::PhaseId,
+    core::{
+        resource::{Res, ResMut, Resource},
+        Type,
+    },
+    system::{local::Local, schedule::PhaseId, SystemArg, WorldAccess},
+    world::{
+        action::{BatchEvents, WorldActionFn},
+        cell::WorldCell,
+        World,
+    },
 };
 use indexmap::{IndexMap, IndexSet};
 use std::{
@@ -29,43 +37,113 @@ impl Into<Type> for EventId {
     }
 }
 
+/// Events are double buffered: [`Events::update`] moves the current events into a
+/// back buffer, where they stay readable by [`EventReader`]s until the next update.
 pub struct Events<E: Event> {
     events: Vec<E>,
+    previous: Vec<E>,
+    events_start: usize,
+    previous_start: usize,
+    count: usize,
+    observed: usize,
     invoked: Arc<Mutex<IndexSet<EventId>>>,
+    consumed: bool,
 }
 
 impl<E: Event> Events<E> {
     pub fn new(invoked: Arc<Mutex<IndexSet<EventId>>>) -> Self {
         Self {
             events: Vec::new(),
+            previous: Vec::new(),
+            events_start: 0,
+            previous_start: 0,
+            count: 0,
+            observed: 0,
             invoked,
+            consumed: false,
         }
     }
 
     pub fn add(&mut self, event: E) {
         self.events.push(event);
+        self.count += 1;
         self.invoked.lock().unwrap().insert(EventId::of::<E>());
     }
 
     pub fn extend(&mut self, events: impl IntoIterator<Item = E>) {
+        let len = self.events.len();
         self.events.extend(events);
+        self.count += self.events.len() - len;
         self.invoked.lock().unwrap().insert(EventId::of::<E>());
     }
 
+    /// The current events that observers haven't run for yet.
     pub fn iter(&self) -> impl Iterator<Item = &E> {
-        self.events.iter()
+        self.unobserved().iter()
+    }
+
+    fn unobserved(&self) -> &[E] {
+        let start = self.observed.saturating_sub(self.events_start);
+        &self.events[start.min(self.events.len())..]
+    }
+
+    /// Events in both buffers that were sent after the given cursor.
+    pub fn iter_from(&self, cursor: usize) -> impl Iterator<Item = &E> {
+        let previous = cursor.saturating_sub(self.previous_start);
+        let current = cursor.saturating_sub(self.events_start);
+        self.previous
+            .iter()
+            .skip(previous)
+            .chain(self.events.iter().skip(current))
+    }
+
+    /// The number of events sent since creation. Used as a reader cursor.
+    pub fn count(&self) -> usize {
+        self.count
     }
 
     pub fn drain(&mut self) -> impl Iterator<Item = E> + '_ {
+        self.events_start = self.count;
         self.events.drain(..)
     }
 
     pub fn take(&mut self) -> Vec<E> {
+        self.events_start = self.count;
         std::mem::take(&mut self.events)
     }
 
+    /// Stops lower priority observers from running for the current events.
+    /// Consumption covers the whole batch sent since observers last ran, not
+    /// a single event: lower priority groups skip every event in the batch.
+    pub fn consume(&mut self) {
+        self.consumed = true;
+    }
+
+    pub fn is_consumed(&self) -> bool {
+        self.consumed
+    }
+
+    /// Marks the current events as observed. They stay readable by
+    /// [`EventReader`]s until the buffers are swapped.
+    pub fn observe(&mut self) {
+        self.observed = self.count;
+        self.consumed = false;
+    }
+
+    /// Moves the current events to the back buffer, dropping the previous ones.
+    pub fn update(&mut self) {
+        self.previous = std::mem::take(&mut self.events);
+        self.previous_start = self.events_start;
+        self.events_start = self.count;
+        self.consumed = false;
+    }
+
     pub fn clear(&mut self) {
         self.events.clear();
+        self.previous.clear();
+        self.events_start = self.count;
+        self.previous_start = self.count;
+        self.consumed = false;
     }
 }
 
@@ -76,13 +154,14 @@ impl<'a, E: Event> IntoIterator for &'a Events<E> {
     type IntoIter = std::slice::Iter<'a, E>;
 
     fn into_iter(self) -> Self::IntoIter {
-        self.events.iter()
+        self.unobserved().iter()
     }
 }
 
 pub struct InvokedEvents {
     invoked: Arc<Mutex<IndexSet<EventId>>>,
-    deferred: Arc<Mutex<IndexMap<PhaseId, IndexSet<EventId>>>>,
+    deferred: Arc<Mutex<IndexMap<PhaseId, Vec<WorldActionFn>>>>,
+    updates: IndexMap<EventId, fn(&mut World)>,
 }
 
 impl InvokedEvents {
@@ -90,28 +169,272 @@ impl InvokedEvents {
         Self {
             invoked: Arc::default(),
             deferred: Arc::default(),
+            updates: IndexMap::new(),
         }
     }
 
+    pub fn register<E: Event>(&mut self) {
+        self.updates.insert(EventId::of::<E>(), |world| {
+            world.resource_mut::<Events<E>>().update();
+        });
+    }
+
+    /// Functions that swap the buffers of each registered event type.
+    pub fn updates(&self) -> impl Iterator<Item = fn(&mut World)> + '_ {
+        self.updates.values().copied()
+    }
+
     pub fn invoke<E: Event>(&self) {
         self.invoked.lock().unwrap().insert(EventId::of::<E>());
     }
 
-    pub fn defer<E: Event>(&self, phase: PhaseId) {
+    /// Holds the event until the world flushes `phase`.
+    pub fn defer<E: Event>(&self, event: E, phase: PhaseId) {
         self.deferred
             .lock()
             .unwrap()
             .entry(phase)
             .or_default()
-            .insert(EventId::of::<E>());
+            .push(BatchEvents::new(vec![event]).into());
     }
 
     pub fn invoked(&self) -> &Arc<Mutex<IndexSet<EventId>>> {
         &self.invoked
     }
 
-    pub fn deferred(&self, phase: PhaseId) -> Option<IndexSet<EventId>> {
-        let mut invoked = self.deferred.lock().unwrap();
-        invoked.shift_remove(&phase)
+    /// Takes the actions that send the events deferred to `phase`.
+    pub fn deferred(&self, phase: PhaseId) -> Vec<WorldActionFn> {
+        let mut deferred = self.deferred.lock().unwrap();
+        deferred.shift_remove(&phase).unwrap_or_default()
+    }
+}
+
+/// Sends events without exclusive world access.
+pub struct EventWriter<'a, E: Event> {
+    events: ResMut<'a, Events<E>>,
+}
+
+impl<'a, E: Event> EventWriter<'a, E> {
+    pub fn send(&mut self, event: E) {
+        self.events.add(event);
+    }
+
+    pub fn send_batch(&mut self, events: impl IntoIterator<Item = E>) {
+        self.events.extend(events);
+    }
+}
+
+impl<E: Event> SystemArg for EventWriter<'_, E> {
+    type Item<'a> = EventWriter<'a, E>;
+
+    fn init(world: &WorldCell) {
+        ResMut::<Events<E>>::init(world);
+    }
+
+    fn get<'a>(world: &'a WorldCell) -> Self::Item<'a> {
+        EventWriter {
+            events: ResMut::<Events<E>>::get(world),
+        }
+    }
+
+    fn access() -> Vec<WorldAccess> {
+        ResMut::<Events<E>>::access()
+    }
+
+    fn done(world: &WorldCell) {
+        ResMut::<Events<E>>::done(world);
+    }
+}
+
+pub struct EventCursor<E: Event> {
+    index: usize,
+    _marker: std::marker::PhantomData<E>,
+}
+
+impl<E: Event> Default for EventCursor<E> {
+    fn default() -> Self {
+        Self {
+            index: 0,
+            _marker: std::marker::PhantomData,
+        }
+    }
+}
+
+/// Reads the events sent since the system last read them.
+/// Each system keeps its own cursor, so every reader sees every event once.
+pub struct EventReader<'a, E: Event> {
+    events: Res<'a, Events<E>>,
+    cursor: Local<'a, EventCursor<E>>,
+}
+
+impl<'a, E: Event> EventReader<'a, E> {
+    pub fn read(&mut self) -> impl Iterator<Item = &E> {
+        let cursor = std::mem::replace(&mut self.cursor.index, self.events.count());
+        self.events.iter_from(cursor)
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.cursor.index == self.events.count()
+    }
+
+    pub fn clear(&mut self) {
+        self.cursor.index = self.events.count();
+    }
+}
+
+impl<E: Event> SystemArg for EventReader<'_, E> {
+    type Item<'a> = EventReader<'a, E>;
+
+    fn init(world: &WorldCell) {
+        Res::<Events<E>>::init(world);
+    }
+
+    fn get<'a>(world: &'a WorldCell) -> Self::Item<'a> {
+        EventReader {
+            events: Res::<Events<E>>::get(world),
+            cursor: Local::<EventCursor<E>>::get(world),
+        }
+    }
+
+    fn access() -> Vec<WorldAccess> {
+        Res::<Events<E>>::access()
+    }
+
+    fn done(world: &WorldCell) {
+        Res::<Events<E>>::done(world);
+        Local::<EventCursor<E>>::done(world);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{Event, EventReader, EventWriter, Events};
+    use crate::{
+        core::resource::{Res, ResMut, Resource},
+        system::schedule::{Phase, PhaseId},
+        world::World,
+    };
+
+    struct Update;
+    impl Phase for Update {}
+
+    struct PostUpdate;
+    impl Phase for PostUpdate {}
+
+    struct Ping(u32);
+    impl Event for Ping {}
+
+    #[derive(Default)]
+    struct Received(Vec<u32>);
+    impl Resource for Received {}
+
+    fn read_once(world: &mut World) -> Vec<u32> {
+        world.add_resource(Received::default());
+        world.run_system_once(
+            |mut reader: EventReader<Ping>, mut received: ResMut<Received>| {
+                received.0.extend(reader.read().map(|ping| ping.0));
+            },
+        );
+        world.remove_resource::<Received>().unwrap().0
+    }
+
+    #[test]
+    fn read_events_once() {
+        let mut world = World::new();
+        world.register_event::<Ping>();
+        world.add_phase::<Update>();
+        world.add_resource(Received::default());
+        world.add_systems(
+            Update,
+            |mut reader: EventReader<Ping>, mut received: ResMut<Received>| {
+                received.0.extend(reader.read().map(|ping| ping.0));
+            },
+        );
+
+        world.run_system_once(|mut writer: EventWriter<Ping>| {
+            writer.send_batch([Ping(0), Ping(1), Ping(2)]);
+        });
+        world.run(Update);
+        world.run(Update);
+
+        assert_eq!(world.resource::<Received>().0, vec![0, 1, 2]);
+    }
+
+    #[test]
+    fn late_reader() {
+        let mut world = World::new();
+        world.register_event::<Ping>();
+
+        world.run_system_once(|mut writer: EventWriter<Ping>| {
+            writer.send_batch([Ping(0), Ping(1), Ping(2)]);
+        });
+        world.resource_mut::<Events<Ping>>().update();
+        assert_eq!(read_once(&mut world), vec![0, 1, 2]);
+
+        world.resource_mut::<Events<Ping>>().update();
+        world.run_system_once(|mut writer: EventWriter<Ping>| writer.send(Ping(3)));
+        assert_eq!(read_once(&mut world), vec![3]);
+    }
+
+    #[test]
+    fn deferred_event() {
+        let mut world = World::new();
+        world.register_event::<Ping>();
+        world.add_phase::<Update>();
+        world.add_phase::<PostUpdate>();
+        world.add_resource(Received::default());
+        world.add_systems(Update, |world: &World| {
+            world.invoke_event_deferred(Ping(0), PhaseId::of::<PostUpdate>());
+        });
+        world.observe::<Ping, _>(
+            |events: Res<Events<Ping>>, mut received: ResMut<Received>| {
+                received.0.extend(events.iter().map(|ping| ping.0));
+            },
+        );
+
+        world.run(Update);
+        assert!(world.resource::<Received>().0.is_empty());
+
+        world.run(PostUpdate);
+        assert_eq!(world.resource::<Received>().0, vec![0]);
+
+        world.run(PostUpdate);
+        assert_eq!(world.resource::<Received>().0, vec![0]);
+    }
+
+    #[test]
+    fn read_observed_events() {
+        #[derive(Default)]
+        struct Observed(Vec<u32>);
+        impl Resource for Observed {}
+
+        let mut world = World::new();
+        world.register_event::<Ping>();
+        world.add_phase::<Update>();
+        world.add_phase::<PostUpdate>();
+        world.add_resource(Observed::default());
+        world.add_resource(Received::default());
+        world.add_systems(Update, |mut writer: EventWriter<Ping>| writer.send(Ping(0)));
+        world.add_systems(PostUpdate, |mut writer: EventWriter<Ping>| {
+            writer.send(Ping(1))
+        });
+        world.observe::<Ping, _>(
+            |events: Res<Events<Ping>>, mut observed: ResMut<Observed>| {
+                observed.0.extend(events.iter().map(|ping| ping.0));
+            },
+        );
+
+        world.run(Update);
+        world.run(PostUpdate);
+        assert_eq!(world.resource::<Observed>().0, vec![0, 1]);
+
+        world.clear_trackers();
+        world.run_system_once(
+            |mut reader: EventReader<Ping>, mut received: ResMut<Received>| {
+                received.0.extend(reader.read().map(|ping| ping.0));
+            },
+        );
+        assert_eq!(world.resource::<Received>().0, vec![0, 1]);
+        assert_eq!(world.resource::<Observed>().0, vec![0, 1]);
     }
 }
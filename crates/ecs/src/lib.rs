@@ -1,6 +1,7 @@
 pub mod archetype;
 pub mod core;
 pub mod event;
+pub mod storage;
 pub mod system;
 pub mod task;
 pub mod world;
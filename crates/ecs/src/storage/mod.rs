@@ -0,0 +1,12 @@
+pub mod sparse;
+
+pub use sparse::SparseSet;
+
+/// Where a component's data is stored.
+/// Sparse set components can be added and removed without moving the entity to a new archetype.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+pub enum StorageKind {
+    #[default]
+    Table,
+    SparseSet,
+}
@@ -0,0 +1,129 @@
+use crate::{
+    archetype::table::{Column, ColumnCell},
+    core::{component::Component, entity::Entity},
+};
+use hashbrown::HashMap;
+
+pub struct SparseSet {
+    components: Column,
+    entities: Vec<Entity>,
+    indices: HashMap<Entity, usize>,
+}
+
+impl SparseSet {
+    pub fn new<C: Component>() -> Self {
+        Self {
+            components: Column::new::<C>(),
+            entities: Vec::new(),
+            indices: HashMap::new(),
+        }
+    }
+
+    pub fn entities(&self) -> &[Entity] {
+        &self.entities
+    }
+
+    pub fn contains(&self, entity: &Entity) -> bool {
+        self.indices.contains_key(entity)
+    }
+
+    pub fn get<C: Component>(&self, entity: &Entity) -> Option<&C> {
+        let index = *self.indices.get(entity)?;
+        self.components.get(index)
+    }
+
+    pub fn get_mut<C: Component>(&mut self, entity: &Entity) -> Option<&mut C> {
+        let index = *self.indices.get(entity)?;
+        self.components.get_mut(index)
+    }
+
+    /// Inserts the component for the entity, returning the previous component if there was one.
+    pub fn insert(&mut self, entity: Entity, component: ColumnCell) -> Option<ColumnCell> {
+        let replaced = self.remove(&entity);
+        self.indices.insert(entity, self.entities.len());
+        self.entities.push(entity);
+        self.components.push_cell(component);
+
+        replaced
+    }
+
+    pub fn remove(&mut self, entity: &Entity) -> Option<ColumnCell> {
+        let index = self.indices.remove(entity)?;
+        let component = self.components.swap_remove_cell(index);
+        self.entities.swap_remove(index);
+        if let Some(moved) = self.entities.get(index) {
+            self.indices.insert(*moved, index);
+        }
+
+        Some(component)
+    }
+
+    pub fn len(&self) -> usize {
+        self.entities.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.entities.is_empty()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        core::component::Component,
+        storage::StorageKind,
+        world::{
+            cell::WorldCell,
+            query::{Not, Query},
+            World,
+        },
+    };
+
+    struct Position(u32);
+    impl Component for Position {}
+
+    struct Selected;
+    impl Component for Selected {}
+
+    #[test]
+    fn toggle_sparse_component() {
+        let mut world = World::new();
+        world
+            .register::<Position>()
+            .register_with::<Selected>(StorageKind::SparseSet);
+
+        let entities = (0..10_000)
+            .map(|index| {
+                let entity = world.spawn();
+                world.add_component(entity, Position(index));
+                entity
+            })
+            .collect::<Vec<_>>();
+
+        let archetypes = world.archetypes().len();
+        for _ in 0..3 {
+            for entity in &entities {
+                let mv = world.add_component(*entity, Selected).unwrap();
+                assert_eq!(mv.from, mv.to);
+            }
+
+            let cell = WorldCell::from(&mut world);
+            let selected = Query::<(&Position, &Selected)>::new(&cell).count();
+            assert_eq!(selected, entities.len());
+
+            for entity in &entities {
+                let mv = world.remove_component::<Selected>(*entity).unwrap();
+                assert_eq!(mv.from, mv.to);
+            }
+
+            let cell = WorldCell::from(&mut world);
+            let unselected = Query::<&Position, Not<Selected>>::new(&cell).count();
+            assert_eq!(unselected, entities.len());
+        }
+
+        assert_eq!(world.archetypes().len(), archetypes);
+
+        let positions = world.query::<&Position>().map(|p| p.0).collect::<Vec<_>>();
+        assert_eq!(positions, (0..10_000).collect::<Vec<_>>());
+    }
+}
@@ -0,0 +1,156 @@
+use super::local::LocalScope;
+use crate::{
+    core::{resource::Resource, Type},
+    world::{cell::WorldCell, World},
+};
+use std::marker::PhantomData;
+
+/// Decides whether a system runs. Conditions are checked right before the system
+/// would run, and a system with several conditions runs only if all of them pass.
+pub trait RunCondition: Send + Sync + 'static {
+    fn should_run(&self, world: &WorldCell) -> bool;
+}
+
+impl<F: Fn(&World) -> bool + Send + Sync + 'static> RunCondition for F {
+    fn should_run(&self, world: &WorldCell) -> bool {
+        self(world.get())
+    }
+}
+
+/// Runs the system only if the resource was accessed mutably since the system last checked it.
+pub struct ResChanged<R: Resource + Send>(PhantomData<fn() -> R>);
+
+impl<R: Resource + Send> ResChanged<R> {
+    pub fn new() -> Self {
+        Self(PhantomData)
+    }
+}
+
+impl<R: Resource + Send> Default for ResChanged<R> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<R: Resource + Send> Clone for ResChanged<R> {
+    fn clone(&self) -> Self {
+        Self::new()
+    }
+}
+
+impl<R: Resource + Send> RunCondition for ResChanged<R> {
+    fn should_run(&self, world: &WorldCell) -> bool {
+        let version = match world.get().resource_version::<R>() {
+            Some(version) => version,
+            None => return false,
+        };
+
+        let locals = match LocalScope::try_current() {
+            Some(locals) => locals,
+            None => return true,
+        };
+
+        let ty = Type::of::<Self>();
+        let changed = version > locals.last_version(ty);
+        locals.set_last_version(ty, version);
+        changed
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::ResChanged;
+    use crate::{
+        core::resource::{ResMut, Resource},
+        system::{schedule::Phase, IntoSystemConfigs},
+        world::World,
+    };
+
+    struct Update;
+    impl Phase for Update {}
+
+    struct Render;
+    impl Phase for Render {}
+
+    struct Config(u32);
+    impl Resource for Config {}
+
+    struct Applied(u32);
+    impl Resource for Applied {}
+
+    #[test]
+    fn run_if_res_changed() {
+        let mut world = World::new();
+        world.add_phase::<Update>();
+        world.add_resource(Config(0));
+        world.add_resource(Applied(0));
+        world.add_systems(
+            Update,
+            (|mut applied: ResMut<Applied>| applied.0 += 1).run_if(ResChanged::<Config>::new()),
+        );
+
+        world.run(Update);
+        world.run(Update);
+        assert_eq!(world.resource::<Applied>().0, 1);
+
+        world.resource_mut::<Config>().0 = 1;
+        world.run(Update);
+        world.run(Update);
+        assert_eq!(world.resource::<Applied>().0, 2);
+    }
+
+    #[test]
+    fn res_changed_after_readd() {
+        let mut world = World::new();
+        world.add_phase::<Update>();
+        world.add_resource(Config(0));
+        world.add_resource(Applied(0));
+        world.add_systems(
+            Update,
+            (|mut applied: ResMut<Applied>| applied.0 += 1).run_if(ResChanged::<Config>::new()),
+        );
+
+        for value in 1..4 {
+            world.resource_mut::<Config>().0 = value;
+            world.run(Update);
+        }
+        assert_eq!(world.resource::<Applied>().0, 3);
+
+        world.remove_resource::<Config>();
+        world.run(Update);
+        world.add_resource(Config(0));
+        world.run(Update);
+        assert_eq!(world.resource::<Applied>().0, 4);
+        assert_eq!(world.resource::<Config>().0, 0);
+    }
+
+    struct Minimized(bool);
+    impl Resource for Minimized {}
+
+    struct Frames(u32, u32);
+    impl Resource for Frames {}
+
+    #[test]
+    fn phase_run_condition() {
+        let mut world = World::new();
+        world.add_phase::<Update>().add_phase::<Render>();
+        world.add_resource(Minimized(true));
+        world.add_resource(Frames(0, 0));
+        world.add_systems(Update, |mut frames: ResMut<Frames>| frames.0 += 1);
+        world.add_systems(Render, |mut frames: ResMut<Frames>| frames.1 += 1);
+        world
+            .add_phase_run_condition(Render, |_: &World| true)
+            .add_phase_run_condition(Render, |world: &World| !world.resource::<Minimized>().0);
+
+        world.run(Update);
+        world.run(Render);
+        let frames = world.resource::<Frames>();
+        assert_eq!((frames.0, frames.1), (1, 0));
+
+        world.resource_mut::<Minimized>().0 = false;
+        world.run(Update);
+        world.run(Render);
+        let frames = world.resource::<Frames>();
+        assert_eq!((frames.0, frames.1), (2, 1));
+    }
+}
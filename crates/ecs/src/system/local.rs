@@ -0,0 +1,177 @@
+use super::SystemArg;
+use crate::{core::Type, world::cell::WorldCell};
+use std::{
+    any::{Any, TypeId},
+    cell::Cell,
+    collections::HashMap,
+    sync::Mutex,
+};
+
+struct LocalValue {
+    value: Box<dyn Any + Send>,
+    borrowed: bool,
+}
+
+/// Per system storage backing `Local` arguments, owned by the system it belongs to.
+#[derive(Default)]
+pub struct SystemLocals {
+    values: Mutex<HashMap<TypeId, LocalValue>>,
+    versions: Mutex<HashMap<Type, u64>>,
+}
+
+impl SystemLocals {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    fn borrow<T: Default + Send + 'static>(&self) -> *mut T {
+        let mut values = self.values.lock().unwrap();
+        let local = values
+            .entry(TypeId::of::<T>())
+            .or_insert_with(|| LocalValue {
+                value: Box::new(T::default()),
+                borrowed: false,
+            });
+
+        if local.borrowed {
+            panic!(
+                "Local<{}> is already borrowed by this system",
+                std::any::type_name::<T>()
+            );
+        }
+
+        local.borrowed = true;
+        local.value.downcast_mut::<T>().unwrap() as *mut T
+    }
+
+    /// The version of a resource when the system last finished with it.
+    pub(crate) fn last_version(&self, ty: Type) -> u64 {
+        let versions = self.versions.lock().unwrap();
+        versions.get(&ty).copied().unwrap_or(0)
+    }
+
+    pub(crate) fn set_last_version(&self, ty: Type, version: u64) {
+        self.versions.lock().unwrap().insert(ty, version);
+    }
+
+    fn release<T: 'static>(&self) {
+        if let Some(local) = self.values.lock().unwrap().get_mut(&TypeId::of::<T>()) {
+            local.borrowed = false;
+        }
+    }
+}
+
+thread_local! {
+    static CURRENT: Cell<Option<*const SystemLocals>> = const { Cell::new(None) };
+}
+
+/// Makes a system's locals available to its `Local` arguments while the system runs.
+pub(crate) struct LocalScope {
+    previous: Option<*const SystemLocals>,
+}
+
+impl LocalScope {
+    pub(crate) fn enter(locals: &SystemLocals) -> Self {
+        let previous = CURRENT.with(|current| current.replace(Some(locals as *const _)));
+        Self { previous }
+    }
+
+    fn current() -> &'static SystemLocals {
+        Self::try_current().expect("Local can only be used as a system argument")
+    }
+
+    pub(crate) fn try_current() -> Option<&'static SystemLocals> {
+        let locals = CURRENT.with(|current| current.get());
+        locals.map(|locals| unsafe { &*locals })
+    }
+}
+
+impl Drop for LocalScope {
+    fn drop(&mut self) {
+        CURRENT.with(|current| current.set(self.previous));
+    }
+}
+
+/// State that persists across runs of a single system.
+/// Initialized with `Default` on the system's first run and private to that system.
+pub struct Local<'a, T: Default + Send + 'static> {
+    value: &'a mut T,
+}
+
+impl<'a, T: Default + Send + 'static> std::ops::Deref for Local<'a, T> {
+    type Target = T;
+
+    fn deref(&self) -> &Self::Target {
+        self.value
+    }
+}
+
+impl<'a, T: Default + Send + 'static> std::ops::DerefMut for Local<'a, T> {
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        self.value
+    }
+}
+
+impl<T: Default + Send + 'static> SystemArg for Local<'_, T> {
+    type Item<'a> = Local<'a, T>;
+
+    fn get<'a>(_: &'a WorldCell) -> Self::Item<'a> {
+        let value = LocalScope::current().borrow::<T>();
+        Local {
+            value: unsafe { &mut *value },
+        }
+    }
+
+    fn done(_: &WorldCell) {
+        LocalScope::current().release::<T>();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::Local;
+    use crate::{
+        core::resource::{ResMut, Resource},
+        system::schedule::Phase,
+        world::World,
+    };
+
+    struct Update;
+    impl Phase for Update {}
+
+    #[derive(Default)]
+    struct Counts {
+        first: u32,
+        second: u32,
+    }
+    impl Resource for Counts {}
+
+    #[test]
+    fn independent_locals() {
+        let mut world = World::new();
+        world.add_phase::<Update>();
+        world.add_resource(Counts::default());
+        world.add_systems(
+            Update,
+            |mut count: Local<u32>, mut counts: ResMut<Counts>| {
+                *count += 1;
+                counts.first = *count;
+            },
+        );
+        world.add_systems(
+            Update,
+            |mut count: Local<u32>, mut counts: ResMut<Counts>| {
+                *count += 1;
+                counts.second = *count;
+            },
+        );
+
+        for _ in 0..3 {
+            world.run(Update);
+        }
+
+        let counts = world.resource::<Counts>();
+        assert_eq!(counts.first, 3);
+        assert_eq!(counts.second, 3);
+    }
+}
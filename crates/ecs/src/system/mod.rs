@@ -2,8 +2,12 @@ use crate::{
     core::{component::ComponentId, entity::Entities, resource::ResourceId, Type},
     world::{cell::WorldCell, World},
 };
-use std::hash::Hash;
+use condition::RunCondition;
+use local::{LocalScope, SystemLocals};
+use std::{hash::Hash, time::Instant};
 
+pub mod condition;
+pub mod local;
 pub mod observer;
 pub mod schedule;
 pub mod systems;
@@ -22,6 +26,10 @@ pub struct System {
     id: SystemId,
     name: Option<&'static str>,
     run: Box<dyn Fn(&WorldCell) + Send + Sync>,
+    access: fn() -> Vec<WorldAccess>,
+    after: Option<SystemId>,
+    conditions: Vec<Box<dyn RunCondition>>,
+    locals: SystemLocals,
 }
 
 impl System {
@@ -30,6 +38,10 @@ impl System {
             id: config.id,
             name: config.name,
             run: config.run,
+            access: config.access,
+            after: config.after,
+            conditions: config.conditions,
+            locals: SystemLocals::new(),
         }
     }
 
@@ -41,8 +53,29 @@ impl System {
         self.name
     }
 
+    pub fn access(&self) -> Vec<WorldAccess> {
+        (self.access)()
+    }
+
+    pub fn after(&self) -> Option<SystemId> {
+        self.after
+    }
+
     pub fn run(&self, world: &WorldCell) {
-        (self.run)(world)
+        let _scope = LocalScope::enter(&self.locals);
+        if !self.conditions.iter().all(|c| c.should_run(world)) {
+            return;
+        }
+
+        let timings = world.get().configs().meta().timings();
+        match timings.enabled() {
+            true => {
+                let start = Instant::now();
+                (self.run)(world);
+                timings.record(self, start.elapsed());
+            }
+            false => (self.run)(world),
+        }
     }
 }
 
@@ -52,6 +85,7 @@ pub struct SystemConfig {
     run: Box<dyn Fn(&WorldCell) + Send + Sync>,
     access: fn() -> Vec<WorldAccess>,
     after: Option<SystemId>,
+    conditions: Vec<Box<dyn RunCondition>>,
     is_send: bool,
 }
 
@@ -68,6 +102,7 @@ impl SystemConfig {
             run,
             access,
             after: None,
+            conditions: Vec::new(),
             is_send,
         }
     }
@@ -103,6 +138,18 @@ pub trait IntoSystemConfigs<M> {
     fn configs(self) -> Vec<SystemConfig>;
     fn before<Marker>(self, systems: impl IntoSystemConfigs<Marker>) -> Vec<SystemConfig>;
     fn after<Marker>(self, systems: impl IntoSystemConfigs<Marker>) -> Vec<SystemConfig>;
+
+    /// Only runs the systems when `condition` passes.
+    fn run_if(self, condition: impl RunCondition + Clone) -> Vec<SystemConfig>
+    where
+        Self: Sized,
+    {
+        let mut configs = self.configs();
+        for config in &mut configs {
+            config.conditions.push(Box::new(condition.clone()));
+        }
+        configs
+    }
 }
 
 impl IntoSystemConfigs<()> for SystemConfig {
@@ -360,8 +407,10 @@ macro_rules! impl_into_system_configs {
             fn configs(self) -> Vec<SystemConfig> {
                 let name = std::any::type_name::<F>();
                 let run = move |world: &WorldCell| {
+                    $($arg::init(world);)*
                     let ($($arg,)*) = ($($arg::get(world),)*);
                     self($($arg),*);
+                    $($arg::done(world);)*
                 };
                 let access = || {
                     let mut metas = Vec::new();
@@ -397,6 +446,10 @@ macro_rules! impl_into_system_configs {
         impl<$($arg: SystemArg),*> SystemArg for ($($arg,)*) {
             type Item<'a> = ($($arg::Item<'a>,)*);
 
+            fn init(world: &WorldCell) {
+                $($arg::init(world);)*
+            }
+
             fn get<'a>(world: &'a WorldCell) -> Self::Item<'a> {
                 ($($arg::get(world),)*)
             }
@@ -410,6 +463,10 @@ macro_rules! impl_into_system_configs {
             fn is_send() -> bool {
                 ($($arg::is_send() &&)* true)
             }
+
+            fn done(world: &WorldCell) {
+                $($arg::done(world);)*
+            }
         }
     };
 }
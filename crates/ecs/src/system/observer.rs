@@ -45,25 +45,31 @@ impl<E: Event> IntoSystemConfigs<()> for Observer<E> {
 }
 
 pub struct EventExtension {
-    clear: fn(&mut World),
+    observe: fn(&mut World),
+    consumed: fn(&World) -> bool,
 }
 
 impl EventExtension {
     pub fn new<E: Event>() -> Self {
         Self {
-            clear: |world| {
-                world.resource_mut::<Events<E>>().clear();
+            observe: |world| {
+                world.resource_mut::<Events<E>>().observe();
             },
+            consumed: |world| world.resource::<Events<E>>().is_consumed(),
         }
     }
 
-    pub fn clear(&self, world: &mut World) {
-        (self.clear)(world);
+    pub fn observe(&self, world: &mut World) {
+        (self.observe)(world);
+    }
+
+    pub fn consumed(&self, world: &World) -> bool {
+        (self.consumed)(world)
     }
 }
 
 pub struct ObserverConfigs {
-    configs: HashMap<EventId, Vec<SystemConfig>>,
+    configs: HashMap<EventId, Vec<(i32, Vec<SystemConfig>)>>,
     extensions: HashMap<EventId, EventExtension>,
 }
 
@@ -75,31 +81,43 @@ impl ObserverConfigs {
         }
     }
 
-    pub fn add<E: Event, M>(&mut self, observers: impl IntoSystemConfigs<M>) {
+    pub fn add<E: Event, M>(&mut self, priority: i32, observers: impl IntoSystemConfigs<M>) {
         let ty = EventId::of::<E>();
-        if let Some(configs) = self.configs.get_mut(&ty) {
-            configs.extend(observers.configs());
-        } else {
-            self.configs.insert(ty, observers.configs());
-            self.extensions.insert(ty, EventExtension::new::<E>());
+        let groups = self.configs.entry(ty).or_default();
+        match groups.iter_mut().find(|(p, _)| *p == priority) {
+            Some((_, configs)) => configs.extend(observers.configs()),
+            None => groups.push((priority, observers.configs())),
         }
+
+        self.extensions
+            .entry(ty)
+            .or_insert_with(EventExtension::new::<E>);
     }
 
     pub fn is_empty(&self) -> bool {
         self.configs.is_empty()
     }
 
-    pub fn into_observers(&mut self, mode: RunMode) -> IndexMap<EventId, SystemGraph> {
-        self.configs
-            .drain()
-            .map(|(ty, configs)| (ty, SystemGraph::new(mode, configs)))
-            .collect()
+    pub fn into_observers(
+        &mut self,
+        mode: RunMode,
+    ) -> impl Iterator<Item = (EventId, Vec<(i32, SystemGraph)>)> + '_ {
+        self.configs.drain().map(move |(ty, groups)| {
+            let groups = groups
+                .into_iter()
+                .map(|(priority, configs)| (priority, SystemGraph::new(mode, configs)))
+                .collect();
+            (ty, groups)
+        })
     }
 }
 
+/// Observers grouped by event. Groups with a higher priority run first, and
+/// an observer can call [`Events::consume`] to skip the remaining groups for
+/// every event sent since the last flush.
 pub struct Observers {
     configs: ObserverConfigs,
-    observers: IndexMap<EventId, SystemGraph>,
+    observers: IndexMap<EventId, Vec<(i32, SystemGraph)>>,
     extensions: HashMap<EventId, EventExtension>,
 }
 
@@ -115,22 +133,136 @@ impl Observers {
     pub fn run(&self, world: WorldCell, invoked: impl IntoIterator<Item = EventId>) {
         let meta = world.get().configs().meta();
         for ty in invoked {
-            if let Some(observers) = self.observers.get(&ty) {
-                meta.runner().run(&world, &[observers]);
-                self.extensions.get(&ty).unwrap().clear(world.get_mut());
+            if let Some(groups) = self.observers.get(&ty) {
+                let extension = self.extensions.get(&ty).unwrap();
+                for (_, observers) in groups {
+                    meta.runner().run(&world, &[observers]);
+                    if extension.consumed(world.get()) {
+                        break;
+                    }
+                }
+
+                extension.observe(world.get_mut());
             }
         }
     }
 
-    pub fn add_observers<E: Event, M>(&mut self, observers: impl IntoSystemConfigs<M>) {
-        self.configs.add::<E, M>(observers);
+    pub fn add_observers<E: Event, M>(
+        &mut self,
+        priority: i32,
+        observers: impl IntoSystemConfigs<M>,
+    ) {
+        self.configs.add::<E, M>(priority, observers);
     }
 
     pub fn build(&mut self, mode: RunMode) {
         if !self.configs.is_empty() {
-            self.observers.extend(self.configs.into_observers(mode));
+            for (ty, groups) in self.configs.into_observers(mode) {
+                let observers = self.observers.entry(ty).or_default();
+                observers.extend(groups);
+                observers.sort_by(|(a, _), (b, _)| b.cmp(a));
+            }
+
             let ext = std::mem::take(&mut self.configs.extensions);
             self.extensions.extend(ext);
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::{IntoSystemConfigs, RunMode, SystemGraph};
+    use crate::{
+        core::resource::{Res, ResMut, Resource},
+        event::{Event, Events},
+        world::World,
+    };
+    use std::{
+        sync::atomic::{AtomicBool, Ordering},
+        time::Duration,
+    };
+
+    struct Click;
+    impl Event for Click {}
+
+    struct Log(Vec<&'static str>);
+    impl Resource for Log {}
+
+    #[test]
+    fn consume_event() {
+        let mut world = World::new();
+        world.register_event::<Click>();
+        world.add_resource(Log(vec![]));
+        world.observe::<Click, _>(|_: Res<Events<Click>>, mut log: ResMut<Log>| {
+            log.0.push("game");
+        });
+        world.observe_with_priority::<Click, _>(
+            10,
+            |mut events: ResMut<Events<Click>>, mut log: ResMut<Log>| {
+                log.0.push("ui");
+                events.consume();
+            },
+        );
+
+        world.invoke_event(Click);
+        world.flush(None);
+
+        assert_eq!(world.resource::<Log>().0, vec!["ui"]);
+        assert!(!world.resource::<Events<Click>>().is_consumed());
+    }
+
+    #[test]
+    fn consume_event_batch() {
+        let mut world = World::new();
+        world.register_event::<Click>();
+        world.add_resource(Log(vec![]));
+        world.observe::<Click, _>(|events: Res<Events<Click>>, mut log: ResMut<Log>| {
+            log.0.extend(events.iter().map(|_| "game"));
+        });
+        world.observe_with_priority::<Click, _>(
+            10,
+            |mut events: ResMut<Events<Click>>, mut log: ResMut<Log>| {
+                log.0.extend(events.iter().map(|_| "ui"));
+                events.consume();
+            },
+        );
+
+        world.invoke_event(Click);
+        world.invoke_event(Click);
+        world.flush(None);
+        assert_eq!(world.resource::<Log>().0, vec!["ui", "ui"]);
+
+        world.invoke_event(Click);
+        world.flush(None);
+        assert_eq!(world.resource::<Log>().0, vec!["ui", "ui", "ui"]);
+    }
+
+    #[test]
+    fn serialize_conflicting_observers() {
+        static RUNNING: AtomicBool = AtomicBool::new(false);
+
+        struct Count(u32);
+        impl Resource for Count {}
+
+        fn observer(mut count: ResMut<Count>) {
+            assert!(!RUNNING.swap(true, Ordering::SeqCst));
+            std::thread::sleep(Duration::from_millis(10));
+            count.0 += 1;
+            RUNNING.store(false, Ordering::SeqCst);
+        }
+
+        let mut world = World::new();
+        world.register_event::<Click>();
+        world.add_resource(Count(0));
+        world.observe::<Click, _>(observer);
+        world.observe::<Click, _>(observer);
+
+        world.invoke_event(Click);
+        world.flush(None);
+
+        assert_eq!(world.resource::<Count>().0, 2);
+
+        let graph = SystemGraph::new(RunMode::Parallel, vec![observer, observer].configs());
+        assert_eq!(graph.groups().len(), 2);
+    }
+}
@@ -1,4 +1,5 @@
 use super::{
+    condition::RunCondition,
     systems::{RunMode, SystemRunner},
     IntoSystemConfigs, System, SystemConfig,
 };
@@ -125,9 +126,11 @@ impl SystemGraph {
                             }
                         }
 
-                        if !has_dependency {
-                            last_group_index = Some(group_index);
+                        if has_dependency {
+                            break;
                         }
+
+                        last_group_index = Some(group_index);
                     }
 
                     match last_group_index {
@@ -172,6 +175,59 @@ impl SystemGraph {
     pub fn groups(&self) -> &[SystemGroup] {
         &self.groups
     }
+
+    /// Writes the graph's systems, stages and ordering edges as DOT statements.
+    /// Explicit `after` edges are solid, edges from conflicting access are dashed.
+    pub fn write_dot(&self, dot: &mut String, prefix: &str) {
+        use std::fmt::Write;
+
+        for (stage, group) in self.groups.iter().enumerate() {
+            let _ = writeln!(dot, "    subgraph cluster_{}{} {{", prefix, stage);
+            let _ = writeln!(dot, "        label=\"stage {}\";", stage);
+            for index in group.indexes() {
+                let name = self.systems[*index].name().unwrap_or("system");
+                let _ = writeln!(dot, "        {}s{} [label=\"{}\"];", prefix, index, name);
+            }
+            let _ = writeln!(dot, "    }}");
+        }
+
+        let accesses = self.systems.iter().map(System::access).collect::<Vec<_>>();
+        for (index, system) in self.systems.iter().enumerate() {
+            let after = system
+                .after()
+                .and_then(|id| self.systems.iter().position(|s| s.id() == id));
+            if let Some(after) = after {
+                let _ = writeln!(dot, "    {0}s{1} -> {0}s{2};", prefix, after, index);
+            }
+
+            for other in (0..index).filter(|other| Some(*other) != after) {
+                if conflicts(&accesses[other], &accesses[index]) {
+                    let _ = writeln!(
+                        dot,
+                        "    {0}s{1} -> {0}s{2} [style=dashed, label=\"conflict\"];",
+                        prefix, other, index
+                    );
+                }
+            }
+        }
+    }
+
+    pub fn dump_graph(&self) -> String {
+        let mut dot = String::from("digraph {\n");
+        self.write_dot(&mut dot, "");
+        dot.push_str("}\n");
+        dot
+    }
+}
+
+fn conflicts(a: &[WorldAccess], b: &[WorldAccess]) -> bool {
+    a.iter().any(|a| {
+        let (ty, access, _) = a.access_ty();
+        b.iter().any(|b| {
+            let (other, other_access, _) = b.access_ty();
+            ty == other && (access == AccessType::Write || other_access == AccessType::Write)
+        })
+    })
 }
 
 pub struct PhaseSystemGraphs {
@@ -308,6 +364,7 @@ impl Default for PhaseRunners {
 pub struct Schedule {
     id: PhaseId,
     children: Vec<Schedule>,
+    conditions: Vec<Box<dyn RunCondition>>,
 }
 
 impl Schedule {
@@ -315,6 +372,7 @@ impl Schedule {
         Self {
             id,
             children: Vec::new(),
+            conditions: Vec::new(),
         }
     }
 
@@ -330,6 +388,15 @@ impl Schedule {
         &self.children
     }
 
+    /// Skips the phase, including its sub phases, unless every condition passes.
+    pub fn add_condition(&mut self, condition: impl RunCondition) {
+        self.conditions.push(Box::new(condition));
+    }
+
+    pub fn should_run(&self, world: &WorldCell) -> bool {
+        self.conditions.iter().all(|c| c.should_run(world))
+    }
+
     pub fn has_child(&self, phase: PhaseId, recursive: bool) -> bool {
         if self.children.iter().any(|c| c.id() == phase) {
             true
@@ -389,4 +456,15 @@ impl Schedule {
             None
         }
     }
+
+    pub fn child_mut(&mut self, id: PhaseId, recursive: bool) -> Option<&mut Schedule> {
+        match self.children.iter().position(|c| c.id() == id) {
+            Some(index) => Some(&mut self.children[index]),
+            None if recursive => self
+                .children
+                .iter_mut()
+                .find_map(|c| c.child_mut(id, recursive)),
+            None => None,
+        }
+    }
 }
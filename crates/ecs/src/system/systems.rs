@@ -5,11 +5,21 @@ use super::{
     },
     IntoSystemConfigs,
 };
-use crate::{core::Type, task::ScopedTaskPool, world::cell::WorldCell};
+use super::{System, SystemId};
+use crate::{
+    core::{resource::Resource, Type},
+    task::ScopedTaskPool,
+    world::cell::WorldCell,
+};
 use indexmap::IndexMap;
 use std::{
+    collections::{HashMap, VecDeque},
     num::NonZero,
-    sync::{Arc, Mutex, MutexGuard},
+    sync::{
+        atomic::{AtomicBool, Ordering},
+        Arc, Mutex, MutexGuard,
+    },
+    time::Duration,
 };
 
 pub trait SystemGroupTag: 'static {}
@@ -177,6 +187,7 @@ pub struct SystemMeta {
     mode: RunMode,
     runner: Arc<dyn SystemRunner>,
     phase_runners: Arc<Mutex<PhaseRunners>>,
+    timings: Arc<SystemTimings>,
 }
 
 impl SystemMeta {
@@ -185,9 +196,14 @@ impl SystemMeta {
             mode,
             runner: mode.runner(),
             phase_runners: Arc::default(),
+            timings: Arc::default(),
         }
     }
 
+    pub(crate) fn timings(&self) -> &SystemTimings {
+        &self.timings
+    }
+
     pub fn mode(&self) -> RunMode {
         self.mode
     }
@@ -230,6 +246,149 @@ impl<'a> std::ops::DerefMut for PhaseRunnersRef<'a> {
     }
 }
 
+/// Run durations of a single system over the profiler's rolling window.
+#[derive(Debug, Clone)]
+pub struct SystemStats {
+    name: &'static str,
+    samples: VecDeque<Duration>,
+}
+
+impl SystemStats {
+    fn new(name: &'static str) -> Self {
+        Self {
+            name,
+            samples: VecDeque::new(),
+        }
+    }
+
+    pub fn name(&self) -> &'static str {
+        self.name
+    }
+
+    pub fn samples(&self) -> usize {
+        self.samples.len()
+    }
+
+    pub fn min(&self) -> Duration {
+        self.samples.iter().min().copied().unwrap_or_default()
+    }
+
+    pub fn max(&self) -> Duration {
+        self.samples.iter().max().copied().unwrap_or_default()
+    }
+
+    pub fn avg(&self) -> Duration {
+        match self.samples.len() {
+            0 => Duration::ZERO,
+            len => self.samples.iter().sum::<Duration>() / len as u32,
+        }
+    }
+}
+
+/// Run durations collected while a phase's systems run. They're moved into the
+/// [`SystemProfiler`] once the systems finish, so systems never touch the resource.
+#[derive(Default)]
+pub(crate) struct SystemTimings {
+    enabled: AtomicBool,
+    samples: Mutex<Vec<(SystemId, Option<&'static str>, Duration)>>,
+}
+
+impl SystemTimings {
+    pub(crate) fn enabled(&self) -> bool {
+        self.enabled.load(Ordering::Relaxed)
+    }
+
+    fn set_enabled(&self, enabled: bool) {
+        self.enabled.store(enabled, Ordering::Relaxed);
+    }
+
+    pub(crate) fn record(&self, system: &System, duration: Duration) {
+        let sample = (system.id(), system.name(), duration);
+        self.samples.lock().unwrap().push(sample);
+    }
+
+    fn drain(&self) -> Vec<(SystemId, Option<&'static str>, Duration)> {
+        std::mem::take(&mut *self.samples.lock().unwrap())
+    }
+}
+
+/// Records how long each system takes to run while enabled.
+/// Systems are only timed when this resource exists and `enabled` is set
+/// at the start of their phase.
+pub struct SystemProfiler {
+    enabled: bool,
+    window: usize,
+    stats: HashMap<SystemId, SystemStats>,
+}
+
+impl SystemProfiler {
+    pub const DEFAULT_WINDOW: usize = 60;
+
+    pub fn new() -> Self {
+        Self {
+            enabled: false,
+            window: Self::DEFAULT_WINDOW,
+            stats: HashMap::new(),
+        }
+    }
+
+    pub fn with_window(mut self, window: usize) -> Self {
+        self.window = window.max(1);
+        self
+    }
+
+    pub fn enabled(&self) -> bool {
+        self.enabled
+    }
+
+    pub fn set_enabled(&mut self, enabled: bool) {
+        self.enabled = enabled;
+    }
+
+    pub fn window(&self) -> usize {
+        self.window
+    }
+
+    pub fn get(&self, id: SystemId) -> Option<&SystemStats> {
+        self.stats.get(&id)
+    }
+
+    pub fn record(&mut self, id: SystemId, name: Option<&'static str>, duration: Duration) {
+        let stats = self
+            .stats
+            .entry(id)
+            .or_insert_with(|| SystemStats::new(name.unwrap_or("<unnamed>")));
+
+        stats.samples.push_back(duration);
+        if stats.samples.len() > self.window {
+            stats.samples.pop_front();
+        }
+    }
+
+    /// The average duration of every recorded system, slowest first.
+    pub fn report(&self) -> impl Iterator<Item = (&'static str, Duration)> {
+        let mut report = self
+            .stats
+            .values()
+            .map(|stats| (stats.name(), stats.avg()))
+            .collect::<Vec<_>>();
+        report.sort_by_key(|(_, avg)| std::cmp::Reverse(*avg));
+        report.into_iter()
+    }
+
+    pub fn clear(&mut self) {
+        self.stats.clear();
+    }
+}
+
+impl Default for SystemProfiler {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Resource for SystemProfiler {}
+
 pub struct Root;
 impl Phase for Root {}
 
@@ -262,6 +421,16 @@ impl Systems {
         self.graphs.add_graphs(graphs);
     }
 
+    /// Describes the phase's systems and their ordering as a DOT graph, for debugging with Graphviz.
+    pub fn dump_graph<P: Phase>(&self, phase: P) -> String {
+        let mut dot = format!("digraph \"{}\" {{\n", std::any::type_name::<P>());
+        for (index, graph) in self.graphs.get(phase.id()).into_iter().enumerate() {
+            graph.write_dot(&mut dot, &format!("g{}_", index));
+        }
+        dot.push_str("}\n");
+        dot
+    }
+
     pub fn run(&self, phase: impl Phase, world: WorldCell) {
         let meta = world.get().configs().meta();
         let runners = meta.phase_runners();
@@ -283,11 +452,29 @@ impl Schedule {
         meta: &SystemMeta,
         runners: &mut PhaseRunnersRef,
     ) {
+        if !self.should_run(world) {
+            return;
+        }
+
         let graphs = systems.graphs().get(self.id());
         if !graphs.is_empty() {
+            let profiler = world.get().try_resource::<SystemProfiler>();
+            let profile = profiler.is_some_and(|profiler| profiler.enabled());
+            meta.timings().set_enabled(profile);
+
             let ctx = RunContext::new(world, &graphs, meta.runner().as_ref());
             let runner = runners.get_mut(&self.id());
             runner.run(ctx);
+
+            if profile {
+                meta.timings().set_enabled(false);
+                let samples = meta.timings().drain();
+                if let Some(profiler) = world.get_mut().try_resource_mut::<SystemProfiler>() {
+                    for (id, name, duration) in samples {
+                        profiler.record(id, name, duration);
+                    }
+                }
+            }
         }
 
         world.get_mut().flush(Some(self.id()));
@@ -310,3 +497,99 @@ impl Schedule {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::SystemProfiler;
+    use crate::{
+        core::{component::Component, resource::ResMut},
+        system::{schedule::Phase, IntoSystemConfigs},
+        world::{query::Query, World},
+    };
+    use std::time::Duration;
+
+    struct Update;
+    impl Phase for Update {}
+
+    fn slow_system() {
+        std::thread::sleep(Duration::from_millis(10));
+    }
+
+    fn fast_system() {}
+
+    fn pause_profiler(mut profiler: ResMut<SystemProfiler>) {
+        profiler.set_enabled(false);
+    }
+
+    #[test]
+    fn profile_systems() {
+        let mut world = World::new();
+        world.add_phase::<Update>();
+        world.add_systems(Update, fast_system);
+        world.add_systems(Update, slow_system);
+
+        let mut profiler = SystemProfiler::new();
+        profiler.set_enabled(true);
+        world.add_resource(profiler);
+
+        for _ in 0..3 {
+            world.run(Update);
+        }
+
+        let report = world
+            .resource::<SystemProfiler>()
+            .report()
+            .collect::<Vec<_>>();
+        assert_eq!(report.len(), 2);
+        assert_eq!(report[0].0, std::any::type_name_of_val(&slow_system));
+        assert!(report[0].1 >= Duration::from_millis(10));
+
+        world.add_systems(Update, pause_profiler);
+        world.run(Update);
+        world.run(Update);
+
+        let profiler = world.resource::<SystemProfiler>();
+        assert!(!profiler.enabled());
+        assert_eq!(profiler.report().count(), 3);
+    }
+
+    struct Counter(u32);
+    impl Component for Counter {}
+
+    fn write_counter(counters: Query<&mut Counter>) {
+        for counter in counters {
+            counter.0 += 1;
+        }
+    }
+
+    fn read_counter(_: Query<&Counter>) {}
+
+    fn first() {}
+
+    fn second() {}
+
+    #[test]
+    fn dump_system_graph() {
+        let mut world = World::new();
+        world.add_phase::<Update>();
+        world.register::<Counter>();
+        world.add_systems(Update, write_counter);
+        world.add_systems(Update, read_counter);
+        world.add_systems(Update, second.after(first));
+
+        let dot = world.dump_graph(Update);
+        assert!(dot.starts_with("digraph"));
+        for system in [
+            std::any::type_name_of_val(&write_counter),
+            std::any::type_name_of_val(&read_counter),
+            std::any::type_name_of_val(&first),
+            std::any::type_name_of_val(&second),
+        ] {
+            assert!(dot.contains(&format!("[label=\"{}\"]", system)));
+        }
+
+        assert!(dot.contains("g0_s0 -> g0_s1 [style=dashed, label=\"conflict\"];"));
+        assert!(dot.contains("g0_s2 -> g0_s3;"));
+        assert!(!dot.contains("g0_s0 -> g0_s2"));
+    }
+}
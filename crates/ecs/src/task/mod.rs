@@ -1,5 +1,14 @@
 use crate::{core::resource::Resource, system::SystemArg};
-use std::{collections::VecDeque, future::Future, pin::Pin, sync::Arc};
+use std::{
+    collections::VecDeque,
+    future::Future,
+    marker::PhantomData,
+    pin::Pin,
+    sync::{
+        atomic::{AtomicBool, Ordering},
+        Arc, Condvar, Mutex,
+    },
+};
 
 pub type ScopedTask<'a> = Box<dyn FnOnce() + Send + 'a>;
 
@@ -62,6 +71,86 @@ impl TaskPool {
     pub fn spawn(&self, task: impl FnOnce() + Send + 'static) {
         self.pool.execute(move || task());
     }
+
+    /// Runs `f` with a [`Scope`] whose tasks can borrow data that outlives the call.
+    /// Every task spawned on the scope finishes before `scope` returns.
+    /// Must not be called from a task running on this pool, since the caller blocks a worker.
+    pub fn scope<'env, R>(&self, f: impl FnOnce(&Scope<'env>) -> R) -> R {
+        let scope = Scope {
+            pool: self.clone(),
+            state: Arc::new(ScopeState::default()),
+            _marker: PhantomData,
+        };
+
+        let result = {
+            let _join = ScopeJoin(&scope.state);
+            f(&scope)
+        };
+
+        if scope.state.panicked.load(Ordering::Acquire) {
+            panic!("A scoped task panicked");
+        }
+
+        result
+    }
+}
+
+#[derive(Default)]
+struct ScopeState {
+    pending: Mutex<usize>,
+    finished: Condvar,
+    panicked: AtomicBool,
+}
+
+/// Waits for the scope's tasks when dropped, so borrowed data outlives them even if `f` panics.
+struct ScopeJoin<'a>(&'a ScopeState);
+
+impl Drop for ScopeJoin<'_> {
+    fn drop(&mut self) {
+        let mut pending = self.0.pending.lock().unwrap();
+        while *pending > 0 {
+            pending = self.0.finished.wait(pending).unwrap();
+        }
+    }
+}
+
+struct ScopedTaskGuard(Arc<ScopeState>);
+
+impl Drop for ScopedTaskGuard {
+    fn drop(&mut self) {
+        if std::thread::panicking() {
+            self.0.panicked.store(true, Ordering::Release);
+        }
+
+        let mut pending = self.0.pending.lock().unwrap();
+        *pending -= 1;
+        if *pending == 0 {
+            self.0.finished.notify_all();
+        }
+    }
+}
+
+/// Spawns tasks on a [`TaskPool`] that may borrow data living for `'env`.
+pub struct Scope<'env> {
+    pool: TaskPool,
+    state: Arc<ScopeState>,
+    _marker: PhantomData<&'env mut &'env ()>,
+}
+
+impl<'env> Scope<'env> {
+    pub fn spawn(&self, task: impl FnOnce() + Send + 'env) {
+        *self.state.pending.lock().unwrap() += 1;
+
+        let task: ScopedTask<'env> = Box::new(task);
+        // The scope waits for every task before returning, so nothing borrowed
+        // for 'env is dropped while a task can still reach it.
+        let task: ScopedTask<'static> = unsafe { std::mem::transmute(task) };
+        let guard = ScopedTaskGuard(self.state.clone());
+        self.pool.pool.execute(move || {
+            let _guard = guard;
+            task();
+        });
+    }
 }
 
 impl Resource for TaskPool {}
@@ -104,3 +193,31 @@ impl SystemArg for &TaskPool {
         world.get().tasks()
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::TaskPool;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+
+    #[test]
+    fn scoped_tasks_borrow() {
+        let pool = TaskPool::new(4);
+        let values = (1..=100).collect::<Vec<usize>>();
+        let total = AtomicUsize::new(0);
+
+        let chunks = pool.scope(|scope| {
+            let mut chunks = 0;
+            for chunk in values.chunks(10) {
+                let total = &total;
+                scope.spawn(move || {
+                    total.fetch_add(chunk.iter().sum(), Ordering::Relaxed);
+                });
+                chunks += 1;
+            }
+            chunks
+        });
+
+        assert_eq!(chunks, 10);
+        assert_eq!(total.load(Ordering::Relaxed), 5050);
+    }
+}
@@ -1,10 +1,11 @@
+use super::World;
 use crate::{
     core::{
         bitset::Bitset,
-        resource::{Res, ResMut, Resource},
+        resource::{Res, ResMut, Resource, ResourceId},
         Type,
     },
-    system::SystemArg,
+    system::{local::LocalScope, AccessType, SystemArg, WorldAccess},
 };
 use std::sync::{Arc, Mutex};
 
@@ -55,6 +56,22 @@ impl WorldAccessTracker {
     }
 }
 
+/// Returns true if the resource changed since the running system last finished with it.
+pub(crate) fn resource_changed<R: Resource + Send>(world: &World) -> bool {
+    let version = world.resource_version::<R>().unwrap_or(0);
+    match LocalScope::try_current() {
+        Some(locals) => version > locals.last_version(Type::of::<R>()),
+        None => true,
+    }
+}
+
+fn record_version<R: Resource + Send>(world: &World) {
+    let version = world.resource_version::<R>().unwrap_or(0);
+    if let Some(locals) = LocalScope::try_current() {
+        locals.set_last_version(Type::of::<R>(), version);
+    }
+}
+
 impl<R: Resource + Send> SystemArg for Res<'_, R> {
     type Item<'a> = Res<'a, R>;
 
@@ -71,10 +88,21 @@ impl<R: Resource + Send> SystemArg for Res<'_, R> {
     }
 
     fn get<'a>(world: &'a crate::world::cell::WorldCell) -> Self::Item<'a> {
-        world.resource::<R>()
+        let changed = resource_changed::<R>(world.get());
+        Res::with_changed(world.get().resource::<R>(), changed)
+    }
+
+    fn access() -> Vec<WorldAccess> {
+        vec![WorldAccess::Resource {
+            ty: ResourceId::of::<R>(),
+            access: AccessType::Read,
+            send: true,
+        }]
     }
 
     fn done(world: &super::cell::WorldCell) {
+        record_version::<R>(world.get());
+
         #[cfg(debug_assertions)]
         {
             let ty = Type::of::<R>();
@@ -100,10 +128,24 @@ impl<R: Resource + Send> SystemArg for ResMut<'_, R> {
     }
 
     fn get<'a>(world: &'a crate::world::cell::WorldCell) -> Self::Item<'a> {
-        world.resource_mut::<R>()
+        let changed = resource_changed::<R>(world.get());
+        let resources = &mut world.get_mut().resources;
+        resources
+            .try_get_tracked::<R>(changed)
+            .unwrap_or_else(|| panic!("Resource {} not found", std::any::type_name::<R>()))
+    }
+
+    fn access() -> Vec<WorldAccess> {
+        vec![WorldAccess::Resource {
+            ty: ResourceId::of::<R>(),
+            access: AccessType::Write,
+            send: true,
+        }]
     }
 
     fn done(world: &super::cell::WorldCell) {
+        record_version::<R>(world.get());
+
         #[cfg(debug_assertions)]
         {
             let ty = Type::of::<R>();
@@ -112,3 +154,120 @@ impl<R: Resource + Send> SystemArg for ResMut<'_, R> {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        core::resource::{Res, ResMut, Resource},
+        system::{
+            schedule::{Phase, SystemGraph},
+            systems::RunMode,
+            AccessType, IntoSystemConfigs, SystemArg,
+        },
+        world::{cell::WorldCell, World},
+    };
+    use std::sync::{Arc, Mutex};
+
+    struct Foo;
+    impl Resource for Foo {}
+
+    struct Bar(u32);
+    impl Resource for Bar {}
+
+    struct Volume(u32);
+    impl Resource for Volume {}
+
+    struct Update;
+    impl Phase for Update {}
+
+    #[test]
+    #[cfg(debug_assertions)]
+    #[should_panic(expected = "is already borrowed")]
+    fn aliased_resource_mut() {
+        let mut world = World::new();
+        world.add_resource(Foo);
+        world.run_system_once(|_: ResMut<Foo>, _: ResMut<Foo>| {});
+    }
+
+    #[test]
+    fn distinct_resource_mut() {
+        let mut world = World::new();
+        world.add_resource(Volume(0));
+        world.add_resource(Bar(0));
+        world.run_system_once(|mut volume: ResMut<Volume>, mut bar: ResMut<Bar>| {
+            volume.0 += 1;
+            bar.0 += 2;
+        });
+
+        let cell = WorldCell::from(&mut world);
+        let (mut volume, mut bar) = cell.resources_mut::<Volume, Bar>();
+        volume.0 += 1;
+        bar.0 += 2;
+
+        assert_eq!(world.resource::<Volume>().0, 2);
+        assert_eq!(world.resource::<Bar>().0, 4);
+    }
+
+    #[test]
+    #[cfg(debug_assertions)]
+    #[should_panic(expected = "can't be borrowed mutably twice")]
+    fn aliased_resources_mut() {
+        let mut world = World::new();
+        world.add_resource(Foo);
+        let _ = WorldCell::from(&mut world).resources_mut::<Foo, Foo>();
+    }
+
+    #[test]
+    fn resource_is_changed() {
+        let mut world = World::new();
+        world.add_phase::<Update>();
+        world.add_resource(Volume(0));
+
+        let writes = Arc::new(Mutex::new(vec![false, true, false]));
+        let changes = Arc::new(Mutex::new(vec![]));
+
+        let frames = writes.clone();
+        world.add_systems(Update, move |mut volume: ResMut<Volume>| {
+            if frames.lock().unwrap().remove(0) {
+                volume.0 += 1;
+            }
+        });
+
+        let seen = changes.clone();
+        world.add_systems(Update, move |volume: Res<Volume>| {
+            seen.lock().unwrap().push(volume.is_changed());
+        });
+
+        for _ in 0..3 {
+            world.run(Update);
+        }
+
+        // The first frame sees the resource being added.
+        assert_eq!(*changes.lock().unwrap(), vec![true, true, false]);
+
+        world.add_resource(Volume(0));
+        writes.lock().unwrap().push(false);
+        world.run(Update);
+        assert!(changes.lock().unwrap()[3]);
+    }
+
+    #[test]
+    fn group_conflicting_resource_access() {
+        fn read_bar(_: Res<Bar>) {}
+        fn write_bar(_: ResMut<Bar>) {}
+
+        let (_, access, _) = Res::<Bar>::access()[0].access_ty();
+        assert_eq!(access, AccessType::Read);
+        let (_, access, _) = ResMut::<Bar>::access()[0].access_ty();
+        assert_eq!(access, AccessType::Write);
+
+        // The second reader can't join the first group without passing the writer.
+        let mut configs = read_bar.configs();
+        configs.extend(write_bar.configs());
+        configs.extend(read_bar.configs());
+        let graph = SystemGraph::new(RunMode::Parallel, configs);
+
+        let groups = graph.groups().iter().map(|g| g.indexes().to_vec());
+        assert_eq!(groups.collect::<Vec<_>>(), vec![vec![0], vec![1], vec![2]]);
+    }
+}
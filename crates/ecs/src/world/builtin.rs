@@ -5,7 +5,10 @@ pub mod actions {
     };
     use crate::{
         archetype::table::Row,
-        core::{component::Component, entity::Entity},
+        core::{
+            component::{Component, ComponentId},
+            entity::Entity,
+        },
         event::Events,
         world::{action::WorldAction, cell::WorldCell, registry::ComponentExtension, World},
     };
@@ -30,25 +33,9 @@ pub mod actions {
     impl WorldAction for Spawn {
         fn execute(self, world: &mut World) -> Option<()> {
             let entity = world.spawn();
-            let mv = world.add_components(entity, self.components)?;
-
+            let mv = world.archetypes.add_components(entity, self.components)?;
             world.resource_mut::<Events<Spawned>>().add(entity.into());
-            let world = WorldCell::from(world);
-            let registry = world.get().registry();
-            for id in mv.added {
-                let hooks = registry.get_extension::<ComponentExtension>(&*id);
-                hooks.on_added(world.get_mut(), entity);
-            }
-
-            for (id, component) in mv.replaced {
-                let hooks = registry.get_extension::<ComponentExtension>(&*id);
-                hooks.on_replaced(world.get_mut(), entity, component);
-            }
-
-            for (id, component) in mv.removed {
-                let hooks = registry.get_extension::<ComponentExtension>(&*id);
-                hooks.on_removed(world.get_mut(), entity, component);
-            }
+            world.run_component_hooks(entity, mv);
 
             Some(())
         }
@@ -66,7 +53,11 @@ pub mod actions {
 
     impl WorldAction for Despawn {
         fn execute(self, world: &mut crate::world::World) -> Option<()> {
-            let row = world.despawn(self.entity)?;
+            if !world.entities.despawn(&self.entity) {
+                return None;
+            }
+
+            let row = world.archetypes.despawn(self.entity)?;
             world
                 .resource_mut::<Events<Despawned>>()
                 .add(self.entity.into());
@@ -88,6 +79,36 @@ pub mod actions {
         }
     }
 
+    pub struct RemoveComponents {
+        entity: Entity,
+        components: Vec<ComponentId>,
+    }
+
+    impl RemoveComponents {
+        pub fn new(entity: Entity) -> Self {
+            Self {
+                entity,
+                components: vec![],
+            }
+        }
+
+        pub fn with<C: Component>(mut self) -> Self {
+            self.components.push(ComponentId::of::<C>());
+            self
+        }
+    }
+
+    impl WorldAction for RemoveComponents {
+        fn execute(self, world: &mut World) -> Option<()> {
+            let mv = world
+                .archetypes
+                .remove_components(self.entity, &self.components)?;
+            world.run_component_hooks(self.entity, mv);
+
+            Some(())
+        }
+    }
+
     pub struct AddChild {
         parent: Entity,
         child: Entity,
@@ -251,9 +272,16 @@ pub mod events {
 
 pub mod components {
     use crate::core::{component::Component, entity::Entity};
+    use std::borrow::Cow;
 
     #[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
     pub struct Parent(Entity);
+    impl Parent {
+        pub fn new(entity: Entity) -> Self {
+            Self(entity)
+        }
+    }
+
     impl std::ops::Deref for Parent {
         type Target = Entity;
         fn deref(&self) -> &Self::Target {
@@ -311,4 +339,33 @@ pub mod components {
     }
 
     impl Component for Children {}
+
+    /// A name used to look up an entity with [`crate::world::World::get_entity_by_name`].
+    #[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+    pub struct Name(Cow<'static, str>);
+
+    impl Name {
+        pub fn new(name: impl Into<Cow<'static, str>>) -> Self {
+            Self(name.into())
+        }
+
+        pub fn as_str(&self) -> &str {
+            &self.0
+        }
+    }
+
+    impl std::ops::Deref for Name {
+        type Target = str;
+        fn deref(&self) -> &Self::Target {
+            &self.0
+        }
+    }
+
+    impl std::fmt::Display for Name {
+        fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+            f.write_str(&self.0)
+        }
+    }
+
+    impl Component for Name {}
 }
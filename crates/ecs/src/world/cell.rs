@@ -1,5 +1,5 @@
 use super::World;
-use crate::core::resource::{Res, ResMut, Resource};
+use crate::core::resource::{Res, ResMut, Resource, ResourceId};
 use std::marker::PhantomData;
 
 /// Provides an unsafe way to access world components and resources.
@@ -23,6 +23,8 @@ impl<'a> WorldCell<'a> {
         Res::new(self.get().resource::<R>())
     }
 
+    /// Callers must not hold two `ResMut`s to the same resource.
+    /// Use [`WorldCell::resources_mut`] to borrow two resources at once.
     #[inline]
     pub fn resource_mut<R: Resource + Send>(&self) -> ResMut<R> {
         ResMut::new(self.get_mut().resource_mut::<R>())
@@ -43,6 +45,8 @@ impl<'a> WorldCell<'a> {
         Res::new(self.get().non_send_resource::<R>())
     }
 
+    /// Callers must not hold two `ResMut`s to the same resource.
+    /// Use [`WorldCell::non_send_resources_mut`] to borrow two resources at once.
     #[inline]
     pub fn non_send_resource_mut<R: Resource>(&self) -> ResMut<R> {
         ResMut::new(self.get_mut().non_send_resource_mut::<R>())
@@ -61,6 +65,42 @@ impl<'a> WorldCell<'a> {
     }
 }
 
+impl<'a> WorldCell<'a> {
+    /// Borrows two distinct resources mutably.
+    /// Panics in debug builds if `A` and `B` are the same resource.
+    pub fn resources_mut<A: Resource + Send, B: Resource + Send>(
+        &self,
+    ) -> (ResMut<'a, A>, ResMut<'a, B>) {
+        debug_assert_ne!(
+            ResourceId::of::<A>(),
+            ResourceId::of::<B>(),
+            "Resource {} can't be borrowed mutably twice",
+            std::any::type_name::<A>()
+        );
+
+        let a = ResMut::new(self.get_mut().resource_mut::<A>());
+        let b = ResMut::new(self.get_mut().resource_mut::<B>());
+        (a, b)
+    }
+
+    /// Borrows two distinct non-send resources mutably.
+    /// Panics in debug builds if `A` and `B` are the same resource.
+    pub fn non_send_resources_mut<A: Resource, B: Resource>(
+        &self,
+    ) -> (ResMut<'a, A>, ResMut<'a, B>) {
+        debug_assert_ne!(
+            ResourceId::of::<A>(),
+            ResourceId::of::<B>(),
+            "Resource {} can't be borrowed mutably twice",
+            std::any::type_name::<A>()
+        );
+
+        let a = ResMut::new(self.get_mut().non_send_resource_mut::<A>());
+        let b = ResMut::new(self.get_mut().non_send_resource_mut::<B>());
+        (a, b)
+    }
+}
+
 impl<'a> From<&mut World> for WorldCell<'a> {
     fn from(world: &mut World) -> Self {
         WorldCell(world as *const _ as *mut _, PhantomData)
@@ -85,7 +125,5 @@ impl<'a> From<&World> for WorldCell<'a> {
     }
 }
 
-impl<'a> WorldCell<'a> {}
-
 unsafe impl<'a> Send for WorldCell<'a> {}
 unsafe impl<'a> Sync for WorldCell<'a> {}
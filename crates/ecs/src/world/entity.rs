@@ -0,0 +1,149 @@
+use super::{action::WorldAction, builtin::actions::Despawn, World};
+use crate::{
+    archetype::table::Row,
+    core::{
+        component::{Component, ComponentId},
+        entity::Entity,
+    },
+};
+use indexmap::IndexSet;
+
+/// Edits a single entity. Inserts and removals are applied together in one archetype
+/// move when the `EntityMut` is dropped or [`apply`](EntityMut::apply) is called.
+pub struct EntityMut<'a> {
+    world: &'a mut World,
+    entity: Entity,
+    added: Row,
+    removed: IndexSet<ComponentId>,
+}
+
+impl<'a> EntityMut<'a> {
+    pub(crate) fn new(world: &'a mut World, entity: Entity) -> Self {
+        Self {
+            world,
+            entity,
+            added: Row::new(),
+            removed: IndexSet::new(),
+        }
+    }
+
+    pub fn id(&self) -> Entity {
+        self.entity
+    }
+
+    pub fn insert<C: Component>(&mut self, component: C) -> &mut Self {
+        self.removed.shift_remove(&ComponentId::of::<C>());
+        self.added.add_component(component);
+        self
+    }
+
+    pub fn remove<C: Component>(&mut self) -> &mut Self {
+        self.added.remove_component::<C>();
+        if self.world.has_component::<C>(self.entity) {
+            self.removed.insert(ComponentId::of::<C>());
+        }
+        self
+    }
+
+    /// Gets a component, including ones inserted but not yet applied.
+    pub fn get<C: Component>(&self) -> Option<&C> {
+        if self.removed.contains(&ComponentId::of::<C>()) {
+            return None;
+        }
+
+        match self.added.get::<C>() {
+            Some(component) => Some(component),
+            None => self.world.get_component::<C>(self.entity),
+        }
+    }
+
+    pub fn get_mut<C: Component>(&mut self) -> Option<&mut C> {
+        if self.removed.contains(&ComponentId::of::<C>()) {
+            return None;
+        }
+
+        match self.added.contains::<C>() {
+            true => self.added.get_mut::<C>(),
+            false => self.world.get_component_mut::<C>(self.entity),
+        }
+    }
+
+    /// Applies the pending inserts and removals, running component hooks.
+    pub fn apply(&mut self) {
+        if self.added.is_empty() && self.removed.is_empty() {
+            return;
+        }
+
+        let added = std::mem::replace(&mut self.added, Row::new());
+        let removed = std::mem::take(&mut self.removed);
+        let mv = self
+            .world
+            .archetypes
+            .modify_components(self.entity, added, removed);
+        if let Some(mv) = mv {
+            self.world.run_component_hooks(self.entity, mv);
+        }
+    }
+
+    /// Despawns the entity, discarding any pending changes.
+    pub fn despawn(mut self) {
+        self.added.clear();
+        self.removed.clear();
+        Despawn::from(self.entity).execute(self.world);
+    }
+}
+
+impl Drop for EntityMut<'_> {
+    fn drop(&mut self) {
+        self.apply();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{core::component::Component, world::World};
+
+    #[derive(Debug, PartialEq)]
+    struct Health(u32);
+    impl Component for Health {}
+
+    #[derive(Debug, PartialEq)]
+    struct Speed(u32);
+    impl Component for Speed {}
+
+    struct Frozen;
+    impl Component for Frozen {}
+
+    #[test]
+    fn entity_mut_single_move() {
+        let mut world = World::new();
+        world
+            .register::<Health>()
+            .register::<Speed>()
+            .register::<Frozen>();
+
+        let entity = world.spawn();
+        world.add_component(entity, Frozen);
+        let archetypes = world.archetypes().len();
+
+        {
+            let mut entity = world.entity_mut(entity);
+            entity
+                .insert(Health(10))
+                .insert(Speed(5))
+                .remove::<Frozen>();
+            assert_eq!(entity.get::<Health>(), Some(&Health(10)));
+            assert!(entity.get::<Frozen>().is_none());
+
+            entity.get_mut::<Speed>().unwrap().0 = 7;
+        }
+
+        assert_eq!(world.archetypes().len(), archetypes + 1);
+        assert_eq!(world.get_component::<Health>(entity), Some(&Health(10)));
+        assert_eq!(world.get_component::<Speed>(entity), Some(&Speed(7)));
+        assert!(!world.has_component::<Frozen>(entity));
+
+        world.entity_mut(entity).despawn();
+        assert!(world.get_entity_mut(entity).is_none());
+    }
+}
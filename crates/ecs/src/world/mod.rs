@@ -7,32 +7,53 @@ use crate::{
         Type,
     },
     event::{Event, EventId, Events, InvokedEvents},
+    storage::StorageKind,
     system::{
+        condition::RunCondition,
         observer::Observers,
-        schedule::{Phase, PhaseId},
+        schedule::{Phase, PhaseId, Schedule},
         systems::{Global, RunMode, SystemConfigs, Systems},
-        IntoSystemConfigs,
+        IntoSystemConfigs, System,
     },
     task::TaskPool,
 };
 use access::WorldAccessTracker;
-use action::WorldActions;
+use action::{WorldAction, WorldActions};
 use builtin::{
-    components::{Children, Parent},
+    actions::Despawn,
+    components::{Children, Name, Parent},
     events::{ComponentUpdate, Despawned, HierarchyUpdate, Spawned},
 };
 use cell::WorldCell;
+use entity::EntityMut;
 use id::WorldId;
-use registry::{Metadata, Registry};
+use name::NameIndex;
+use query::{BaseQuery, Query, QueryFilter, ReadQuery};
+use registry::{ComponentExtension, ComponentHooks, Metadata, Registry, ResourceExtension};
+use scene::ResourceSnapshot;
 
 pub mod access;
 pub mod action;
 pub mod builtin;
 pub mod cell;
+pub mod entity;
+pub mod name;
 pub mod query;
 pub mod registry;
+pub mod scene;
 pub mod spawner;
 
+/// Creates a value with access to the world. Implemented for all `Default` types.
+pub trait FromWorld {
+    fn from_world(world: &mut World) -> Self;
+}
+
+impl<T: Default> FromWorld for T {
+    fn from_world(_: &mut World) -> Self {
+        T::default()
+    }
+}
+
 pub struct World {
     id: WorldId,
     access: WorldAccessTracker,
@@ -47,6 +68,7 @@ pub struct World {
     systems: Systems,
     observers: Observers,
     tasks: TaskPool,
+    names: NameIndex,
 }
 
 impl World {
@@ -65,6 +87,7 @@ impl World {
             systems: Systems::new(),
             observers: Observers::new(),
             tasks: TaskPool::default(),
+            names: NameIndex::new(),
         };
 
         world.register_builtin();
@@ -131,6 +154,11 @@ impl World {
         self.resources.get_mut::<R>()
     }
 
+    /// The resource's version, which is bumped every time it's accessed mutably.
+    pub fn resource_version<R: Resource + Send>(&self) -> Option<u64> {
+        self.resources.version::<R>()
+    }
+
     pub fn non_send_resource<R: Resource>(&self) -> &R {
         self.non_send_resources.get::<R>()
     }
@@ -156,14 +184,102 @@ impl World {
     }
 
     pub fn register<C: Component>(&mut self) -> &mut Self {
-        self.archetypes.register_component::<C>();
+        self.register_with::<C>(StorageKind::Table)
+    }
+
+    pub fn register_with<C: Component>(&mut self, storage: StorageKind) -> &mut Self {
+        self.archetypes.register_component_with::<C>(storage);
         self.registry.register_component::<C>();
         self.register_event::<ComponentUpdate<C>>()
     }
 
+    /// Runs `hook` after a component of type `C` is added to any entity.
+    pub fn on_add<C: Component>(
+        &mut self,
+        hook: impl Fn(&World, Entity) + Send + Sync + 'static,
+    ) -> &mut Self {
+        let ty = Type::of::<C>();
+        if !self.registry.contains(&ty) {
+            self.register::<C>();
+        }
+
+        self.registry
+            .get_extension_mut::<ComponentExtension>(&ty)
+            .hooks_mut()
+            .on_add(hook);
+        self
+    }
+
+    /// Runs `hook` after a component of type `C` is removed from any entity,
+    /// including when the entity is despawned.
+    pub fn on_remove<C: Component>(
+        &mut self,
+        hook: impl Fn(&World, Entity) + Send + Sync + 'static,
+    ) -> &mut Self {
+        let ty = Type::of::<C>();
+        if !self.registry.contains(&ty) {
+            self.register::<C>();
+        }
+
+        self.registry
+            .get_extension_mut::<ComponentExtension>(&ty)
+            .hooks_mut()
+            .on_remove(hook);
+        self
+    }
+
+    /// Runs `hook` after a component of type `C` is replaced on any entity.
+    pub fn on_replace<C: Component>(
+        &mut self,
+        hook: impl Fn(&World, Entity) + Send + Sync + 'static,
+    ) -> &mut Self {
+        let ty = Type::of::<C>();
+        if !self.registry.contains(&ty) {
+            self.register::<C>();
+        }
+
+        self.registry
+            .get_extension_mut::<ComponentExtension>(&ty)
+            .hooks_mut()
+            .on_replace(hook);
+        self
+    }
+
+    /// Registers a component that can be saved to and loaded from a [`scene::Scene`].
+    pub fn register_serialize<C>(&mut self) -> &mut Self
+    where
+        C: Component + serde::Serialize + serde::de::DeserializeOwned,
+    {
+        self.register::<C>();
+        self.registry.register_serialize::<C>();
+        self
+    }
+
+    /// Registers a resource that can be saved with [`World::save_resources`].
+    pub fn register_serializable_resource<R>(&mut self) -> &mut Self
+    where
+        R: Resource + Send + serde::Serialize + serde::de::DeserializeOwned,
+    {
+        self.registry.register_serializable_resource::<R>();
+        self
+    }
+
+    /// Serializes the registered serializable resources to RON.
+    pub fn save_resources(&self) -> ron::Result<String> {
+        ResourceSnapshot::from_world(self)?.to_ron()
+    }
+
+    /// Restores resources saved with [`World::save_resources`].
+    /// Resources that aren't registered are skipped and returned by name.
+    pub fn load_resources(&mut self, ron: &str) -> ron::Result<Vec<String>> {
+        ResourceSnapshot::from_ron(ron)?.apply(self)
+    }
+
     pub fn register_event<E: Event>(&mut self) -> &mut Self {
         let invoked = self.events.invoked();
         self.resources.add(Events::<E>::new(invoked.clone()));
+        self.registry.register_resource::<Events<E>>();
+        self.events.register::<E>();
         self
     }
 
@@ -178,35 +294,118 @@ impl World {
     }
 
     pub fn init_resource<R: Resource + Default + Send>(&mut self) -> &mut Self {
-        self.resources.add(R::default());
-        self.registry.register_resource::<R>();
-        self
+        self.add_resource(R::default())
     }
 
+    /// Adds the resource, replacing any existing one. Replacing doesn't run the added hooks.
     pub fn add_resource<R: Resource + Send>(&mut self, resource: R) -> &mut Self {
+        let added = !self.resources.contains::<R>();
         self.resources.add(resource);
-        self.registry.register_resource::<R>();
+        let ty = self.registry.register_resource::<R>();
+        if added {
+            self.resource_added(ty);
+        }
         self
     }
 
+    /// Creates the resource with [`FromWorld`] and adds it.
+    /// The resource is not in the world while `from_world` runs.
+    pub fn init_resource_from<R: Resource + FromWorld + Send>(&mut self) -> &mut Self {
+        let resource = R::from_world(self);
+        self.add_resource(resource)
+    }
+
     pub fn init_non_send_resource<R: Resource + Default>(&mut self) -> &mut Self {
-        self.non_send_resources.add(R::default());
-        self.registry.register_resource::<R>();
-        self
+        self.add_non_send_resource(R::default())
     }
 
     pub fn add_non_send_resource<R: Resource>(&mut self, resource: R) -> &mut Self {
+        let added = !self.non_send_resources.contains::<R>();
         self.non_send_resources.add(resource);
-        self.registry.register_resource::<R>();
+        let ty = self.registry.register_resource::<R>();
+        if added {
+            self.resource_added(ty);
+        }
         self
     }
 
+    /// Gets the resource, adding the one returned by `f` if it doesn't exist yet.
+    pub fn resource_or_insert_with<R: Resource + Send>(&mut self, f: impl FnOnce() -> R) -> &mut R {
+        if !self.resources.contains::<R>() {
+            self.add_resource(f());
+        }
+
+        self.resources.get_mut::<R>()
+    }
+
+    /// Gets the non send resource, adding the one returned by `f` if it doesn't exist yet.
+    pub fn non_send_resource_or_insert_with<R: Resource>(
+        &mut self,
+        f: impl FnOnce() -> R,
+    ) -> &mut R {
+        if !self.non_send_resources.contains::<R>() {
+            self.add_non_send_resource(f());
+        }
+
+        self.non_send_resources.get_mut::<R>()
+    }
+
     pub fn remove_resource<R: Resource + Send>(&mut self) -> Option<R> {
-        self.resources.remove::<R>()
+        let resource = self.resources.remove::<R>()?;
+        self.resource_removed(Type::of::<R>());
+        Some(resource)
     }
 
     pub fn remove_non_send_resource<R: Resource>(&mut self) -> Option<R> {
-        self.non_send_resources.remove::<R>()
+        let resource = self.non_send_resources.remove::<R>()?;
+        self.resource_removed(Type::of::<R>());
+        Some(resource)
+    }
+
+    /// Runs `hook` after a resource of type `R` is added to the world.
+    /// Replacing an existing resource doesn't run it.
+    pub fn on_resource_added<R: Resource>(
+        &mut self,
+        hook: impl Fn(&mut World) + Send + Sync + 'static,
+    ) -> &mut Self {
+        let ty = self.registry.register_resource::<R>();
+        self.registry
+            .get_extension_mut::<ResourceExtension>(&ty)
+            .on_add(hook);
+        self
+    }
+
+    /// Runs `hook` after a resource of type `R` is removed from the world.
+    pub fn on_resource_removed<R: Resource>(
+        &mut self,
+        hook: impl Fn(&mut World) + Send + Sync + 'static,
+    ) -> &mut Self {
+        let ty = self.registry.register_resource::<R>();
+        self.registry
+            .get_extension_mut::<ResourceExtension>(&ty)
+            .on_remove(hook);
+        self
+    }
+
+    fn resource_hooks(&self, ty: &Type) -> Option<&ResourceExtension> {
+        match self.registry.contains(ty) {
+            true => self.registry.get(ty).extension().downcast_ref(),
+            false => None,
+        }
+    }
+
+    fn resource_added(&mut self, ty: Type) {
+        let world = WorldCell::from(self);
+        if let Some(hooks) = world.get().resource_hooks(&ty) {
+            hooks.on_added(world.get_mut());
+        }
+    }
+
+    fn resource_removed(&mut self, ty: Type) {
+        let world = WorldCell::from(self);
+        if let Some(hooks) = world.get().resource_hooks(&ty) {
+            hooks.on_removed(world.get_mut());
+        }
     }
 
     pub fn invoke_event<E: Event>(&mut self, event: E) -> &mut Self {
@@ -215,6 +414,12 @@ impl World {
         self
     }
 
+    /// Sends the event when `phase` is flushed, so its observers only run in that phase.
+    pub fn invoke_event_deferred<E: Event>(&self, event: E, phase: PhaseId) -> &Self {
+        self.events.defer(event, phase);
+        self
+    }
+
     pub fn add_systems<M>(
         &mut self,
         phase: impl Phase,
@@ -226,7 +431,17 @@ impl World {
     }
 
     pub fn observe<E: Event, M>(&mut self, observers: impl IntoSystemConfigs<M>) -> &mut Self {
-        self.observers.add_observers::<E, M>(observers);
+        self.observers.add_observers::<E, M>(0, observers);
+        self
+    }
+
+    /// Adds observers that run before observers with a lower priority.
+    pub fn observe_with_priority<E: Event, M>(
+        &mut self,
+        priority: i32,
+        observers: impl IntoSystemConfigs<M>,
+    ) -> &mut Self {
+        self.observers.add_observers::<E, M>(priority, observers);
         self
     }
 
@@ -235,6 +450,36 @@ impl World {
         self
     }
 
+    pub fn add_schedule(&mut self, schedule: Schedule) -> &mut Self {
+        self.systems.schedule_mut().add_child(schedule);
+        self
+    }
+
+    /// Skips the phase and its sub phases unless every condition added to it passes.
+    /// Panics if the phase hasn't been added.
+    pub fn add_phase_run_condition<P: Phase>(
+        &mut self,
+        phase: P,
+        condition: impl RunCondition,
+    ) -> &mut Self {
+        let id = phase.id();
+        let schedule = self.systems.schedule_mut();
+        let schedule = match schedule.id() == id {
+            true => schedule,
+            false => schedule
+                .child_mut(id, true)
+                .unwrap_or_else(|| panic!("Phase {} not found", std::any::type_name::<P>())),
+        };
+
+        schedule.add_condition(condition);
+        self
+    }
+
+    pub fn has_phase(&self, phase: PhaseId) -> bool {
+        let schedule = self.systems.schedule();
+        schedule.id() == phase || schedule.has_child(phase, true)
+    }
+
     pub fn add_sub_phase<Main: Phase, Sub: Phase>(&mut self) -> &mut Self {
         self.systems.schedule_mut().add_sub_child::<Main, Sub>();
         self
@@ -258,18 +503,47 @@ impl World {
         self.systems.run(phase, WorldCell::from(self as &Self));
     }
 
+    /// Returns the phase's system graph in DOT format. See [`Systems::dump_graph`].
+    pub fn dump_graph(&mut self, phase: impl Phase) -> String {
+        if !self.configs.is_empty() {
+            self.systems.add_graphs(self.configs.build_graphs());
+        }
+
+        self.systems.dump_graph(phase)
+    }
+
+    /// Runs a single system immediately, outside of any phase, then flushes the world.
+    pub fn run_system_once<M>(&mut self, system: impl IntoSystemConfigs<M>) {
+        let mut configs = system.configs();
+        assert_eq!(
+            configs.len(),
+            1,
+            "run_system_once expects exactly one system"
+        );
+
+        let system = System::new(configs.remove(0));
+        system.run(&WorldCell::from(self as &Self));
+        self.flush(None);
+    }
+
+    /// Executes an action immediately instead of queueing it for the next flush.
+    pub fn apply_action(&mut self, action: impl WorldAction) -> Option<()> {
+        action.execute(self)
+    }
+
     pub fn flush(&mut self, phase: Option<PhaseId>) {
         self.actions.drain().drain(..).for_each(|a| a.execute(self));
+        if let Some(phase) = phase {
+            self.events
+                .deferred(phase)
+                .drain(..)
+                .for_each(|a| a.execute(self));
+        }
 
         let invoked = {
             let invoked = self.events.invoked();
             let mut invoked = invoked.lock().unwrap();
-            let mut invoked = invoked.drain(..).collect::<Vec<_>>();
-            if let Some(phases) = phase.and_then(|p| self.events.deferred(p)) {
-                invoked.extend(phases);
-            }
-
-            invoked
+            invoked.drain(..).collect::<Vec<_>>()
         };
 
         self.observers.build(self.mode());
@@ -285,9 +559,29 @@ impl World {
         }
     }
 
+    /// The most recently named entity with the given [`Name`].
+    pub fn get_entity_by_name(&self, name: &str) -> Option<Entity> {
+        self.names.get(name)
+    }
+
+    /// Every entity with the given [`Name`], in the order they were named.
+    pub fn entities_by_name(&self, name: &str) -> Vec<Entity> {
+        self.names.get_all(name)
+    }
+
+    fn index_name(&self, entity: Entity) {
+        if let Some(name) = self.get_component::<Name>(entity) {
+            self.names.insert(name, entity);
+        }
+    }
+
     fn register_builtin(&mut self) {
         self.register::<Parent>();
         self.register::<Children>();
+        self.register::<Name>();
+        self.on_add::<Name>(Self::index_name);
+        self.on_replace::<Name>(Self::index_name);
+        self.on_remove::<Name>(|world, entity| world.names.remove(entity));
         self.register_event::<Spawned>();
         self.register_event::<Despawned>();
         self.register_event::<HierarchyUpdate>();
@@ -306,14 +600,58 @@ impl World {
         entity
     }
 
+    /// Despawns the entity and returns its components. Runs the `on_remove` hooks,
+    /// but not the `Despawn` action's events or child despawns.
     pub fn despawn(&mut self, entity: Entity) -> Option<Row> {
-        if self.entities.despawn(&entity) {
-            self.archetypes.remove_entity(entity).map(|(_, row)| row)
-        } else {
-            None
+        if !self.entities.despawn(&entity) {
+            return None;
+        }
+
+        let row = self.archetypes.despawn(entity)?;
+        for id in row.ids() {
+            self.component_hooks(id).removed(self, entity);
+        }
+
+        Some(row)
+    }
+
+    /// Despawns every entity, running component hooks and sending `Despawned`
+    /// events as a regular despawn would. Resources are left untouched.
+    pub fn clear_entities(&mut self) {
+        let entities = self.archetypes.entities().copied().collect::<Vec<_>>();
+        for entity in entities {
+            Despawn::from(entity).execute(self);
+        }
+
+        // Ids reserved by queued spawns keep the allocator from restarting.
+        if self.entities.is_empty() {
+            self.entities.reset();
+        }
+    }
+
+    /// Resets per frame tracking state. Events move to their back buffer,
+    /// so events older than the previous frame are dropped.
+    pub fn clear_trackers(&mut self) {
+        let updates = self.events.updates().collect::<Vec<_>>();
+        for update in updates {
+            update(self);
         }
     }
 
+    /// Edits a single entity, batching its inserts and removals into one archetype move.
+    /// Panics if the entity isn't alive.
+    pub fn entity_mut(&mut self, entity: Entity) -> EntityMut<'_> {
+        self.get_entity_mut(entity)
+            .unwrap_or_else(|| panic!("Entity {:?} does not exist", entity))
+    }
+
+    pub fn get_entity_mut(&mut self, entity: Entity) -> Option<EntityMut<'_>> {
+        self.archetypes
+            .entity_archetype(entity)
+            .is_some()
+            .then(|| EntityMut::new(self, entity))
+    }
+
     pub fn has_component<C: Component>(&self, entity: Entity) -> bool {
         self.archetypes.has_component::<C>(entity)
     }
@@ -339,15 +677,21 @@ impl World {
         entity: Entity,
         component: C,
     ) -> Option<EntityMove> {
-        self.archetypes.add_component(entity, component)
+        let mv = self.archetypes.add_component(entity, component)?;
+        self.run_move_hooks(entity, &mv);
+        Some(mv)
     }
 
     pub fn add_components(&mut self, entity: Entity, components: Row) -> Option<EntityMove> {
-        self.archetypes.add_components(entity, components)
+        let mv = self.archetypes.add_components(entity, components)?;
+        self.run_move_hooks(entity, &mv);
+        Some(mv)
     }
 
     pub fn remove_component<C: Component>(&mut self, entity: Entity) -> Option<EntityMove> {
-        self.archetypes.remove_component::<C>(entity)
+        let mv = self.archetypes.remove_component::<C>(entity)?;
+        self.run_move_hooks(entity, &mv);
+        Some(mv)
     }
 
     pub fn remove_components(
@@ -355,7 +699,69 @@ impl World {
         entity: Entity,
         components: impl IntoIterator<Item = impl AsRef<ComponentId>>,
     ) -> Option<EntityMove> {
-        self.archetypes.remove_components(entity, components)
+        let mv = self.archetypes.remove_components(entity, components)?;
+        self.run_move_hooks(entity, &mv);
+        Some(mv)
+    }
+
+    /// Runs the user component hooks for a move whose components are handed back to the caller.
+    /// `ComponentUpdate` events aren't sent since they'd take the moved components.
+    fn run_move_hooks(&self, entity: Entity, mv: &EntityMove) {
+        for id in &mv.added {
+            self.component_hooks(id).added(self, entity);
+        }
+
+        for id in mv.replaced.ids() {
+            self.component_hooks(id).replaced(self, entity);
+        }
+
+        for id in mv.removed.ids() {
+            self.component_hooks(id).removed(self, entity);
+        }
+    }
+
+    fn component_hooks(&self, id: &ComponentId) -> &ComponentHooks {
+        self.registry
+            .get_extension::<ComponentExtension>(id)
+            .hooks()
+    }
+
+    /// Runs the component hooks for everything an archetype move added, replaced or removed.
+    pub(crate) fn run_component_hooks(&mut self, entity: Entity, mv: EntityMove) {
+        let world = WorldCell::from(&*self);
+        let registry = world.get().registry();
+        for id in mv.added {
+            let hooks = registry.get_extension::<ComponentExtension>(&id);
+            hooks.on_added(world.get_mut(), entity);
+        }
+
+        for (id, component) in mv.replaced {
+            let hooks = registry.get_extension::<ComponentExtension>(&id);
+            hooks.on_replaced(world.get_mut(), entity, component);
+        }
+
+        for (id, component) in mv.removed {
+            let hooks = registry.get_extension::<ComponentExtension>(&id);
+            hooks.on_removed(world.get_mut(), entity, component);
+        }
+    }
+
+    /// Queries the world outside of a system. The world can't be mutated while the query is alive.
+    pub fn query<Q: ReadQuery>(&self) -> Query<'_, Q> {
+        self.query_filtered::<Q, ()>()
+    }
+
+    pub fn query_filtered<Q: ReadQuery, F: QueryFilter>(&self) -> Query<'_, Q, F> {
+        Query::new(&WorldCell::from(self)).released()
+    }
+
+    /// Queries the world outside of a system, allowing components to be mutated.
+    pub fn query_mut<Q: BaseQuery>(&mut self) -> Query<'_, Q> {
+        self.query_filtered_mut::<Q, ()>()
+    }
+
+    pub fn query_filtered_mut<Q: BaseQuery, F: QueryFilter>(&mut self) -> Query<'_, Q, F> {
+        Query::new(&WorldCell::from(self)).released()
     }
 }
 
@@ -381,3 +787,292 @@ pub mod id {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::{
+        builtin::{
+            actions::{Despawn, RemoveComponents, Spawn},
+            components::Name,
+            events::{Despawned, Spawned},
+        },
+        query::Query,
+        spawner::Spawner,
+        FromWorld, World,
+    };
+    use crate::{
+        archetype::table::Row,
+        core::{component::Component, entity::Entity, resource::Resource},
+        event::Events,
+    };
+    use std::sync::{Arc, Mutex};
+
+    struct Age(u32);
+    impl Component for Age {}
+
+    #[test]
+    fn run_system_once() {
+        let mut world = World::new();
+        world.register::<Age>();
+        world.run_system_once(|mut spawner: Spawner| {
+            spawner.spawn().with(Age(10));
+        });
+
+        world.run_system_once(|query: Query<(Entity, &Age)>| {
+            let ages = query.map(|(_, age)| age.0).collect::<Vec<_>>();
+            assert_eq!(ages, vec![10]);
+        });
+    }
+
+    #[test]
+    fn apply_action() {
+        let mut world = World::new();
+        world.register::<Age>();
+
+        assert!(world.apply_action(Spawn::new().with(Age(5))).is_some());
+
+        let spawned = world.resource::<Events<Spawned>>();
+        let entity = spawned
+            .iter()
+            .flat_map(|s| s.iter())
+            .copied()
+            .next()
+            .unwrap();
+        assert_eq!(world.get_component::<Age>(entity).map(|a| a.0), Some(5));
+    }
+
+    struct Speed(u32);
+    impl Resource for Speed {}
+
+    struct Boost(u32);
+    impl Resource for Boost {}
+    impl FromWorld for Boost {
+        fn from_world(world: &mut World) -> Self {
+            Boost(world.resource::<Speed>().0 * 2)
+        }
+    }
+
+    #[test]
+    fn clear_entities() {
+        let mut world = World::new();
+        world.register::<Age>();
+        world.add_resource(Speed(3));
+        world.run_system_once(|mut spawner: Spawner| {
+            for age in 0..3 {
+                spawner.spawn().with(Age(age));
+            }
+        });
+
+        world.clear_entities();
+
+        world.run_system_once(|query: Query<&Age>| assert_eq!(query.count(), 0));
+        assert!(world.entities().is_empty());
+        assert_eq!(world.resource::<Speed>().0, 3);
+
+        let despawned = world.resource::<Events<Despawned>>();
+        assert_eq!(despawned.iter().map(|d| d.len()).sum::<usize>(), 3);
+
+        world.clear_trackers();
+        assert_eq!(world.resource::<Events<Despawned>>().iter().count(), 0);
+    }
+
+    struct Health(u32);
+    impl Component for Health {}
+
+    #[test]
+    fn component_hooks() {
+        let added = Arc::new(Mutex::new(vec![]));
+        let removed = Arc::new(Mutex::new(vec![]));
+
+        let mut world = World::new();
+        world.register::<Age>();
+        let hook_added = added.clone();
+        world.on_add::<Health>(move |world, entity| {
+            assert!(world.has_component::<Health>(entity));
+            hook_added.lock().unwrap().push(entity);
+        });
+        let hook_removed = removed.clone();
+        world.on_remove::<Health>(move |world, entity| {
+            assert!(!world.has_component::<Health>(entity));
+            hook_removed.lock().unwrap().push(entity);
+        });
+
+        world.apply_action(Spawn::new().with(Health(10)).with(Age(1)));
+        world.apply_action(Spawn::new().with(Health(20)));
+        let spawned = world
+            .resource::<Events<Spawned>>()
+            .iter()
+            .flat_map(|s| s.iter())
+            .copied()
+            .collect::<Vec<_>>();
+        assert_eq!(*added.lock().unwrap(), spawned);
+        let health = world.get_component::<Health>(spawned[1]);
+        assert_eq!(health.map(|h| h.0), Some(20));
+
+        world.apply_action(RemoveComponents::new(spawned[0]).with::<Health>());
+        assert_eq!(*removed.lock().unwrap(), vec![spawned[0]]);
+        assert!(world.has_component::<Age>(spawned[0]));
+
+        world.apply_action(Despawn::from(spawned[1]));
+        assert_eq!(*removed.lock().unwrap(), spawned);
+    }
+
+    #[test]
+    fn direct_component_hooks() {
+        let added = Arc::new(Mutex::new(vec![]));
+        let removed = Arc::new(Mutex::new(vec![]));
+
+        let mut world = World::new();
+        let hook_added = added.clone();
+        world.on_add::<Age>(move |_, entity| hook_added.lock().unwrap().push(entity));
+        let hook_removed = removed.clone();
+        world.on_remove::<Age>(move |world, entity| {
+            assert!(!world.has_component::<Age>(entity));
+            hook_removed.lock().unwrap().push(entity);
+        });
+
+        let first = world.spawn();
+        let second = world.spawn();
+        world.add_component(first, Age(1));
+        let mut row = Row::new();
+        row.add_component(Age(2));
+        world.add_components(second, row);
+        assert_eq!(*added.lock().unwrap(), vec![first, second]);
+
+        world.remove_component::<Age>(first);
+        assert_eq!(*removed.lock().unwrap(), vec![first]);
+
+        let row = world.despawn(second).unwrap();
+        assert_eq!(row.get::<Age>().map(|a| a.0), Some(2));
+        assert!(world.despawn(second).is_none());
+        assert_eq!(*removed.lock().unwrap(), vec![first, second]);
+    }
+
+    #[test]
+    fn query_world() {
+        let mut world = World::new();
+        world.register::<Age>();
+        let entities = (0..3)
+            .map(|age| {
+                let entity = world.spawn();
+                world.add_component(entity, Age(age));
+                entity
+            })
+            .collect::<Vec<_>>();
+
+        let queried = world
+            .query::<(Entity, &Age)>()
+            .map(|(entity, _)| entity)
+            .collect::<Vec<_>>();
+        assert_eq!(queried, entities);
+
+        for age in world.query_mut::<&mut Age>() {
+            age.0 += 1;
+        }
+
+        let ages = world.query::<&Age>().map(|a| a.0).collect::<Vec<_>>();
+        assert_eq!(ages, vec![1, 2, 3]);
+    }
+
+    #[test]
+    fn init_resource_from() {
+        let mut world = World::new();
+        world.add_resource(Speed(3)).init_resource_from::<Boost>();
+
+        assert_eq!(world.resource::<Boost>().0, 6);
+    }
+
+    #[test]
+    fn non_send_resource_or_insert_with() {
+        struct EventLoop(std::rc::Rc<u32>);
+        impl Resource for EventLoop {}
+
+        let mut world = World::new();
+        let mut created = 0;
+        for _ in 0..2 {
+            world.non_send_resource_or_insert_with(|| {
+                created += 1;
+                EventLoop(std::rc::Rc::new(1))
+            });
+        }
+
+        assert_eq!(created, 1);
+        assert_eq!(*world.non_send_resource::<EventLoop>().0, 1);
+    }
+
+    #[test]
+    fn entity_by_name() {
+        let mut world = World::new();
+        let player = world.spawn();
+        world.entity_mut(player).insert(Name::new("player"));
+        let enemy = world.spawn();
+        world.entity_mut(enemy).insert(Name::new("enemy"));
+
+        assert_eq!(world.get_entity_by_name("player"), Some(player));
+        assert_eq!(world.get_entity_by_name("enemy"), Some(enemy));
+
+        world.entity_mut(enemy).insert(Name::new("player"));
+        assert_eq!(world.get_entity_by_name("enemy"), None);
+        assert_eq!(world.get_entity_by_name("player"), Some(enemy));
+        assert_eq!(world.entities_by_name("player"), vec![player, enemy]);
+
+        world.apply_action(RemoveComponents::new(enemy).with::<Name>());
+        assert_eq!(world.entities_by_name("player"), vec![player]);
+
+        world.apply_action(Despawn::from(player));
+        assert_eq!(world.get_entity_by_name("player"), None);
+    }
+
+    #[test]
+    fn entity_by_name_direct() {
+        let mut world = World::new();
+        let player = world.spawn();
+        world.add_component(player, Name::new("player"));
+        let enemy = world.spawn();
+        world.add_component(enemy, Name::new("enemy"));
+
+        assert_eq!(world.get_entity_by_name("player"), Some(player));
+        assert_eq!(world.get_entity_by_name("enemy"), Some(enemy));
+
+        world.add_component(player, Name::new("hero"));
+        assert_eq!(world.get_entity_by_name("player"), None);
+        assert_eq!(world.get_entity_by_name("hero"), Some(player));
+
+        world.remove_component::<Name>(player);
+        assert_eq!(world.get_entity_by_name("hero"), None);
+
+        world.despawn(enemy);
+        assert_eq!(world.get_entity_by_name("enemy"), None);
+        assert!(world.entities_by_name("enemy").is_empty());
+    }
+
+    #[derive(Default)]
+    struct Hooked {
+        added: u32,
+        removed: u32,
+    }
+    impl Resource for Hooked {}
+
+    struct Device;
+    impl Resource for Device {}
+
+    #[test]
+    fn resource_hooks() {
+        let mut world = World::new();
+        world.init_resource::<Hooked>();
+        world
+            .on_resource_added::<Device>(|world| world.resource_mut::<Hooked>().added += 1)
+            .on_resource_removed::<Device>(|world| world.resource_mut::<Hooked>().removed += 1);
+
+        world.add_resource(Device);
+        world.add_resource(Device);
+        world.resource_or_insert_with(|| Device);
+        assert_eq!(world.resource::<Hooked>().added, 1);
+        assert_eq!(world.resource::<Hooked>().removed, 0);
+
+        assert!(world.remove_resource::<Device>().is_some());
+        assert!(world.remove_resource::<Device>().is_none());
+        assert_eq!(world.resource::<Hooked>().added, 1);
+        assert_eq!(world.resource::<Hooked>().removed, 1);
+    }
+}
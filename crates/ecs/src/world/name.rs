@@ -0,0 +1,65 @@
+use crate::core::entity::Entity;
+use hashbrown::HashMap;
+use std::sync::Mutex;
+
+/// Maps [`super::builtin::components::Name`]s to the entities that have them.
+/// Kept in sync by the `Name` component hooks.
+#[derive(Default)]
+pub struct NameIndex {
+    inner: Mutex<NameIndexInner>,
+}
+
+#[derive(Default)]
+struct NameIndexInner {
+    entities: HashMap<String, Vec<Entity>>,
+    names: HashMap<Entity, String>,
+}
+
+impl NameIndex {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn insert(&self, name: &str, entity: Entity) {
+        let mut inner = self.inner.lock().unwrap();
+        inner.remove(entity);
+        inner
+            .entities
+            .entry(name.to_string())
+            .or_default()
+            .push(entity);
+        inner.names.insert(entity, name.to_string());
+    }
+
+    pub fn remove(&self, entity: Entity) {
+        self.inner.lock().unwrap().remove(entity);
+    }
+
+    /// The most recently named entity with the given name.
+    pub fn get(&self, name: &str) -> Option<Entity> {
+        let inner = self.inner.lock().unwrap();
+        inner.entities.get(name).and_then(|e| e.last().copied())
+    }
+
+    /// Every entity with the given name, in the order they were named.
+    pub fn get_all(&self, name: &str) -> Vec<Entity> {
+        let inner = self.inner.lock().unwrap();
+        inner.entities.get(name).cloned().unwrap_or_default()
+    }
+}
+
+impl NameIndexInner {
+    fn remove(&mut self, entity: Entity) {
+        let name = match self.names.remove(&entity) {
+            Some(name) => name,
+            None => return,
+        };
+
+        if let Some(entities) = self.entities.get_mut(&name) {
+            entities.retain(|e| *e != entity);
+            if entities.is_empty() {
+                self.entities.remove(&name);
+            }
+        }
+    }
+}
@@ -20,12 +20,15 @@ pub struct QueryCursor<'a> {
 
 impl<'a> QueryCursor<'a> {
     pub fn new(archetypes: IndexSet<&'a Archetype>) -> Self {
-        Self {
+        let mut cursor = Self {
             archetypes,
             entity: 0,
             archetype: 0,
             row: None,
-        }
+        };
+
+        cursor.skip_empty();
+        cursor
     }
 
     pub fn archetypes(&self) -> &IndexSet<&'a Archetype> {
@@ -37,7 +40,8 @@ impl<'a> QueryCursor<'a> {
     }
 
     pub fn entity(&self) -> Option<&Entity> {
-        self.archetypes[self.archetype]
+        self.archetypes
+            .get_index(self.archetype)?
             .table()
             .entities()
             .get_index(self.entity)
@@ -58,6 +62,18 @@ impl<'a> QueryCursor<'a> {
             let entity = archetype.table().entities()[self.entity];
             self.row = archetype.table().select(&entity);
             self.entity += 1;
+            self.skip_empty();
+        }
+    }
+
+    fn skip_empty(&mut self) {
+        while let Some(archetype) = self.archetypes.get_index(self.archetype) {
+            if self.entity < archetype.table().entities().len() {
+                break;
+            }
+
+            self.archetype += 1;
+            self.entity = 0;
         }
     }
 }
@@ -66,27 +82,26 @@ pub trait BaseQuery: Send + Sync {
     type Item<'a>: Send + Sync;
 
     fn init(_: &World, _: &mut QueryState) {}
-    fn fetch<'a>(world: &'a WorldCell, entity: Entity) -> Self::Item<'a>;
+    fn fetch<'a>(world: WorldCell<'a>, entity: Entity) -> Self::Item<'a>;
     fn access() -> Vec<WorldAccess>;
 }
 
+/// A query that only reads from the world, so it can be run through a shared world reference.
+pub trait ReadQuery: BaseQuery {}
+
+impl<C: Component> ReadQuery for &C {}
+impl<C: Component> ReadQuery for Option<&C> {}
+impl ReadQuery for Entity {}
+
 impl<C: Component> BaseQuery for &C {
     type Item<'a> = &'a C;
 
     fn init(world: &World, state: &mut QueryState) {
-        let id = ComponentId::of::<C>();
-        #[cfg(debug_assertions)]
-        {
-            let index = world.registry().index_of(&*id);
-            if !world.access().read(index) {
-                let meta = world.registry().get(&*id);
-                panic!("Component {} is already borrowed", meta.name());
-            }
-        }
-        state.add_component(id);
+        borrow_component::<C>(world, AccessType::Read);
+        state.add_component(ComponentId::of::<C>());
     }
 
-    fn fetch<'a>(world: &'a WorldCell, entity: Entity) -> Self::Item<'a> {
+    fn fetch<'a>(world: WorldCell<'a>, entity: Entity) -> Self::Item<'a> {
         world.get().get_component(entity).unwrap()
     }
 
@@ -102,19 +117,11 @@ impl<C: Component> BaseQuery for &mut C {
     type Item<'a> = &'a mut C;
 
     fn init(world: &World, state: &mut QueryState) {
-        let id = ComponentId::of::<C>();
-        #[cfg(debug_assertions)]
-        {
-            let index = world.registry().index_of(&*id);
-            if !world.access().write(index) {
-                let meta = world.registry().get(&*id);
-                panic!("Component {} is already borrowed", meta.name());
-            }
-        }
-        state.add_component(id);
+        borrow_component::<C>(world, AccessType::Write);
+        state.add_component(ComponentId::of::<C>());
     }
 
-    fn fetch<'a>(world: &'a WorldCell, entity: Entity) -> Self::Item<'a> {
+    fn fetch<'a>(world: WorldCell<'a>, entity: Entity) -> Self::Item<'a> {
         world.get_mut().get_component_mut(entity).unwrap()
     }
 
@@ -129,11 +136,12 @@ impl<C: Component> BaseQuery for &mut C {
 impl<C: Component> BaseQuery for Option<&C> {
     type Item<'a> = Option<&'a C>;
 
-    fn init(world: &World, state: &mut QueryState) {
-        <&C as BaseQuery>::init(world, state);
+    /// Optional components are borrowed but don't filter the matched entities.
+    fn init(world: &World, _: &mut QueryState) {
+        borrow_component::<C>(world, AccessType::Read);
     }
 
-    fn fetch<'a>(world: &'a WorldCell, entity: Entity) -> Self::Item<'a> {
+    fn fetch<'a>(world: WorldCell<'a>, entity: Entity) -> Self::Item<'a> {
         world.get().get_component(entity)
     }
 
@@ -145,11 +153,11 @@ impl<C: Component> BaseQuery for Option<&C> {
 impl<C: Component> BaseQuery for Option<&mut C> {
     type Item<'a> = Option<&'a mut C>;
 
-    fn init(world: &World, state: &mut QueryState) {
-        <&mut C as BaseQuery>::init(world, state);
+    fn init(world: &World, _: &mut QueryState) {
+        borrow_component::<C>(world, AccessType::Write);
     }
 
-    fn fetch<'a>(world: &'a WorldCell, entity: Entity) -> Self::Item<'a> {
+    fn fetch<'a>(world: WorldCell<'a>, entity: Entity) -> Self::Item<'a> {
         world.get_mut().get_component_mut(entity)
     }
 
@@ -158,10 +166,27 @@ impl<C: Component> BaseQuery for Option<&mut C> {
     }
 }
 
+fn borrow_component<C: Component>(world: &World, access: AccessType) {
+    #[cfg(debug_assertions)]
+    {
+        let id = ComponentId::of::<C>();
+        let index = world.registry().index_of(&id);
+        let borrowed = match access {
+            AccessType::Read => world.access().read(index),
+            AccessType::Write => world.access().write(index),
+        };
+
+        if !borrowed {
+            let meta = world.registry().get(&id);
+            panic!("Component {} is already borrowed", meta.name());
+        }
+    }
+}
+
 impl BaseQuery for Entity {
     type Item<'a> = Entity;
 
-    fn fetch<'a>(_: &'a WorldCell, entity: Entity) -> Self::Item<'a> {
+    fn fetch<'a>(_: WorldCell<'a>, entity: Entity) -> Self::Item<'a> {
         entity
     }
 
@@ -174,6 +199,7 @@ pub trait QueryFilter {
     fn init(world: &World, state: &mut QueryState);
 }
 
+/// Matches entities that have `C` without borrowing it.
 pub struct With<C: Component> {
     _marker: std::marker::PhantomData<C>,
 }
@@ -184,6 +210,7 @@ impl<C: Component> QueryFilter for With<C> {
     }
 }
 
+/// Matches entities that don't have `C`.
 pub struct Not<C: Component> {
     _marker: std::marker::PhantomData<C>,
 }
@@ -194,42 +221,168 @@ impl<C: Component> QueryFilter for Not<C> {
     }
 }
 
+pub type Without<C> = Not<C>;
+
 impl QueryFilter for () {
     fn init(_: &World, _: &mut QueryState) {}
 }
 
 pub struct Query<'a, Q: BaseQuery, F: QueryFilter = ()> {
-    world: &'a WorldCell<'a>,
+    world: WorldCell<'a>,
     cursor: QueryCursor<'a>,
+    state: QueryState,
+    sparse: QueryState,
+    release: bool,
     _marker: std::marker::PhantomData<(Q, F)>,
 }
 
 impl<'a, Q: BaseQuery, F: QueryFilter> Query<'a, Q, F> {
-    pub fn new(world: &'a WorldCell) -> Self {
+    pub fn new(world: &WorldCell<'a>) -> Self {
+        let world = *world;
         let mut state = QueryState::new();
         Q::init(world.get(), &mut state);
         F::init(world.get(), &mut state);
 
-        let archetypes = world
-            .get()
-            .archetypes()
-            .query(state.components(), state.excluded());
-        let cursor = QueryCursor::new(archetypes);
+        let archetypes = world.get().archetypes();
+        let sparse = state.take_sparse(archetypes);
+        let cursor = QueryCursor::new(archetypes.query(state.components(), state.excluded()));
 
         Self {
             world,
             cursor,
+            state,
+            sparse,
+            release: false,
             _marker: std::marker::PhantomData,
         }
     }
 
+    /// Releases the query's component access when it is dropped,
+    /// for queries that aren't run as a system argument.
+    pub(crate) fn released(mut self) -> Self {
+        self.release = true;
+        self
+    }
+
     pub fn filter(&self, entities: &'a [Entity]) -> FilterQuery<'a, Q, F> {
-        FilterQuery::new(self.world, entities)
+        FilterQuery::new(&self.world, entities)
+    }
+
+    /// Iterates over every unordered combination of `K` distinct entities matched by the query.
+    pub fn iter_combinations<const K: usize>(&self) -> QueryCombinations<'a, Q, K>
+    where
+        Q: ReadQuery,
+    {
+        QueryCombinations::new(self.world, self.entities())
+    }
+
+    /// Like [`Query::iter_combinations`], but lends out mutable items one combination at a time.
+    pub fn iter_combinations_mut<const K: usize>(&mut self) -> QueryCombinationsMut<'_, Q, K> {
+        QueryCombinationsMut(QueryCombinations::new(self.world, self.entities()))
+    }
+
+    /// Every entity matched by the query, regardless of how far it has been iterated.
+    fn entities(&self) -> Vec<Entity> {
+        let archetypes = self.world.get().archetypes();
+        let mut entities = vec![];
+        for archetype in archetypes.query(self.state.components(), self.state.excluded()) {
+            for entity in archetype.table().entities() {
+                if self.sparse.matches(archetypes, *entity) {
+                    entities.push(*entity);
+                }
+            }
+        }
+
+        entities
+    }
+
+    /// Returns the only item matched by the query.
+    pub fn single(mut self) -> Result<Q::Item<'a>, QuerySingleError> {
+        let item = self.next().ok_or(QuerySingleError::NoEntities)?;
+        match self.count() {
+            0 => Ok(item),
+            count => Err(QuerySingleError::MultipleEntities { count: count + 1 }),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum QuerySingleError {
+    NoEntities,
+    MultipleEntities { count: usize },
+}
+
+impl std::fmt::Display for QuerySingleError {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        match self {
+            QuerySingleError::NoEntities => write!(f, "Query matched no entities"),
+            QuerySingleError::MultipleEntities { count } => {
+                write!(f, "Query matched {} entities, expected one", count)
+            }
+        }
+    }
+}
+
+impl std::error::Error for QuerySingleError {}
+
+pub struct QueryCombinations<'a, Q: BaseQuery, const K: usize> {
+    world: WorldCell<'a>,
+    entities: Vec<Entity>,
+    indices: Option<[usize; K]>,
+    _marker: std::marker::PhantomData<Q>,
+}
+
+impl<'a, Q: BaseQuery, const K: usize> QueryCombinations<'a, Q, K> {
+    fn new(world: WorldCell<'a>, entities: Vec<Entity>) -> Self {
+        let indices = (K > 0 && K <= entities.len()).then(|| std::array::from_fn(|i| i));
+        Self {
+            world,
+            entities,
+            indices,
+            _marker: std::marker::PhantomData,
+        }
+    }
+
+    /// Returns the current combination and moves to the next one in lexicographic order.
+    fn advance(&mut self) -> Option<[Entity; K]> {
+        let indices = self.indices?;
+        let len = self.entities.len();
+        self.indices = (0..K).rev().find(|&i| indices[i] < len - K + i).map(|i| {
+            let mut next = indices;
+            next[i] += 1;
+            for j in i + 1..K {
+                next[j] = next[j - 1] + 1;
+            }
+            next
+        });
+
+        Some(indices.map(|index| self.entities[index]))
+    }
+}
+
+impl<'a, Q: ReadQuery, const K: usize> Iterator for QueryCombinations<'a, Q, K> {
+    type Item = [Q::Item<'a>; K];
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let entities = self.advance()?;
+        Some(entities.map(|entity| Q::fetch(self.world, entity)))
+    }
+}
+
+/// Combinations of mutable query items. Each combination borrows distinct entities,
+/// and is released before the next one is fetched.
+pub struct QueryCombinationsMut<'a, Q: BaseQuery, const K: usize>(QueryCombinations<'a, Q, K>);
+
+impl<'a, Q: BaseQuery, const K: usize> QueryCombinationsMut<'a, Q, K> {
+    pub fn fetch_next(&mut self) -> Option<[Q::Item<'_>; K]> {
+        let entities = self.0.advance()?;
+        let world: WorldCell<'_> = self.0.world;
+        Some(entities.map(|entity| Q::fetch(world, entity)))
     }
 }
 
 pub struct FilterQuery<'a, Q: BaseQuery, F: QueryFilter = ()> {
-    world: &'a WorldCell<'a>,
+    world: WorldCell<'a>,
     archetypes: IndexSet<&'a Archetype>,
     archetype: usize,
     entity: usize,
@@ -238,7 +391,8 @@ pub struct FilterQuery<'a, Q: BaseQuery, F: QueryFilter = ()> {
 }
 
 impl<'a, Q: BaseQuery, F: QueryFilter> FilterQuery<'a, Q, F> {
-    pub fn new(world: &'a WorldCell, entities: &'a [Entity]) -> Self {
+    pub fn new(world: &WorldCell<'a>, entities: &'a [Entity]) -> Self {
+        let world = *world;
         let mut state = QueryState::new();
         Q::init(world.get(), &mut state);
         F::init(world.get(), &mut state);
@@ -288,20 +442,55 @@ impl QueryState {
     pub fn excluded(&self) -> &[ComponentId] {
         &self.excluded
     }
+
+    /// Moves sparse set components into a separate state, since they are matched
+    /// per entity instead of per archetype.
+    pub fn take_sparse(&mut self, archetypes: &Archetypes) -> QueryState {
+        let mut sparse = QueryState::new();
+        let (components, excluded) = (
+            std::mem::take(&mut self.components),
+            std::mem::take(&mut self.excluded),
+        );
+
+        for component in components {
+            match archetypes.is_sparse(&component) {
+                true => sparse.add_component(component),
+                false => self.add_component(component),
+            }
+        }
+
+        for component in excluded {
+            match archetypes.is_sparse(&component) {
+                true => sparse.exclude(component),
+                false => self.exclude(component),
+            }
+        }
+
+        sparse
+    }
+
+    pub fn matches(&self, archetypes: &Archetypes, entity: Entity) -> bool {
+        let has = |component: &ComponentId| {
+            archetypes
+                .sparse_set(component)
+                .is_some_and(|set| set.contains(&entity))
+        };
+
+        self.components.iter().all(has) && !self.excluded.iter().any(has)
+    }
 }
 
 impl<'a, Q: BaseQuery, F: QueryFilter> Iterator for Query<'a, Q, F> {
     type Item = Q::Item<'a>;
 
     fn next(&mut self) -> Option<Self::Item> {
-        if self.cursor.archetypes().is_empty() {
-            return None;
+        loop {
+            let entity = *self.cursor.entity()?;
+            self.cursor.next();
+            if self.sparse.matches(self.world.get().archetypes(), entity) {
+                return Some(Q::fetch(self.world, entity));
+            }
         }
-
-        let entity = self.cursor.entity()?;
-        let item = Q::fetch(&self.world, *entity);
-        self.cursor.next();
-        Some(item)
     }
 }
 
@@ -318,7 +507,7 @@ impl<'a, Q: BaseQuery, F: QueryFilter> Iterator for FilterQuery<'a, Q, F> {
         }
 
         let entity = self.entities[self.entity];
-        let item = Q::fetch(&self.world, entity);
+        let item = Q::fetch(self.world, entity);
         self.entity += 1;
         Some(item)
     }
@@ -341,6 +530,30 @@ impl<Q: BaseQuery, F: QueryFilter> SystemArg for Query<'_, Q, F> {
 
         access
     }
+
+    fn done(world: &WorldCell) {
+        #[cfg(debug_assertions)]
+        release_access::<Q>(world);
+    }
+}
+
+impl<'a, Q: BaseQuery, F: QueryFilter> Drop for Query<'a, Q, F> {
+    fn drop(&mut self) {
+        #[cfg(debug_assertions)]
+        if self.release {
+            release_access::<Q>(&self.world);
+        }
+    }
+}
+
+#[cfg(debug_assertions)]
+fn release_access<Q: BaseQuery>(world: &WorldCell) {
+    for access in Q::access() {
+        if let WorldAccess::Component { ty, .. } = access {
+            let index = world.get().registry().index_of(&ty);
+            world.get().access().clear(index);
+        }
+    }
 }
 
 #[macro_export]
@@ -356,7 +569,7 @@ macro_rules! impl_base_query_for_tuples {
                     )+
                 }
 
-                fn fetch<'a>(world: &'a WorldCell<'a>, entity: Entity) -> Self::Item<'a> {
+                fn fetch<'a>(world: WorldCell<'a>, entity: Entity) -> Self::Item<'a> {
                     ($($name::fetch(world, entity),)+)
                 }
 
@@ -368,6 +581,8 @@ macro_rules! impl_base_query_for_tuples {
                     metas
                 }
             }
+
+            impl<$($name: ReadQuery),+> ReadQuery for ($($name,)+) {}
         )+
     };
 }
@@ -375,7 +590,7 @@ macro_rules! impl_base_query_for_tuples {
 #[macro_export]
 macro_rules! impl_filter_query_for_tuple {
     ($($filter:ident),*) => {
-        impl<$($filter: FilterQuery),*> FilterQuery for ($($filter,)*) {
+        impl<$($filter: QueryFilter),*> QueryFilter for ($($filter,)*) {
             fn init(world: &World, state: &mut QueryState) {
                 $(
                     $filter::init(world, state);
@@ -385,6 +600,14 @@ macro_rules! impl_filter_query_for_tuple {
     };
 }
 
+impl_filter_query_for_tuple!(A, B);
+impl_filter_query_for_tuple!(A, B, C);
+impl_filter_query_for_tuple!(A, B, C, D);
+impl_filter_query_for_tuple!(A, B, C, D, E);
+impl_filter_query_for_tuple!(A, B, C, D, E, F);
+impl_filter_query_for_tuple!(A, B, C, D, E, F, G);
+impl_filter_query_for_tuple!(A, B, C, D, E, F, G, H);
+
 impl_base_query_for_tuples!((A, B));
 impl_base_query_for_tuples!((A, B, C));
 impl_base_query_for_tuples!((A, B, C, D));
@@ -401,3 +624,181 @@ impl_base_query_for_tuples!((A, B, C, D, E, F, G, H, I, J, K, L, M, N));
 impl_base_query_for_tuples!((A, B, C, D, E, F, G, H, I, J, K, L, M, N, O));
 impl_base_query_for_tuples!((A, B, C, D, E, F, G, H, I, J, K, L, M, N, O, P));
 impl_base_query_for_tuples!((A, B, C, D, E, F, G, H, I, J, K, L, M, N, O, P, Q));
+
+#[cfg(test)]
+mod tests {
+    use super::{Query, QuerySingleError, With, Without};
+    use crate::{
+        core::{component::Component, entity::Entity},
+        world::{cell::WorldCell, World},
+    };
+
+    struct Player;
+    impl Component for Player {}
+
+    fn single(world: &mut World) -> Result<Entity, QuerySingleError> {
+        let world = WorldCell::from(world);
+        let query = Query::<(Entity, &Player)>::new(&world);
+        query.single().map(|(entity, _)| entity)
+    }
+
+    #[test]
+    fn query_single() {
+        let mut world = World::new();
+        world.register::<Player>();
+        assert_eq!(single(&mut world), Err(QuerySingleError::NoEntities));
+
+        let player = world.spawn();
+        world.add_component(player, Player);
+        assert_eq!(single(&mut world), Ok(player));
+
+        let other = world.spawn();
+        world.add_component(other, Player);
+        assert_eq!(
+            single(&mut world),
+            Err(QuerySingleError::MultipleEntities { count: 2 })
+        );
+    }
+
+    struct Score(u32);
+    impl Component for Score {}
+
+    #[test]
+    fn sequential_mut_queries() {
+        let mut world = World::new();
+        world.register::<Score>();
+        let entity = world.spawn();
+        world.add_component(entity, Score(0));
+
+        for _ in 0..2 {
+            world.run_system_once(|query: Query<&mut Score>| {
+                for score in query {
+                    score.0 += 1;
+                }
+            });
+        }
+
+        assert_eq!(world.get_component::<Score>(entity).map(|s| s.0), Some(2));
+    }
+
+    struct Enemy;
+    impl Component for Enemy {}
+
+    #[test]
+    fn query_multiple_archetypes() {
+        let mut world = World::new();
+        world.register::<Player>().register::<Enemy>();
+
+        for index in 0..3 {
+            let entity = world.spawn();
+            world.add_component(entity, Player);
+            if index == 1 {
+                world.add_component(entity, Enemy);
+            }
+        }
+
+        let world = WorldCell::from(&mut world);
+        assert_eq!(Query::<&Player>::new(&world).count(), 3);
+    }
+
+    struct Velocity(u32);
+    impl Component for Velocity {}
+
+    #[test]
+    fn query_optional_component() {
+        let mut world = World::new();
+        world.register::<Player>().register::<Velocity>();
+
+        let moving = world.spawn();
+        world.add_component(moving, Player);
+        world.add_component(moving, Velocity(5));
+        let idle = world.spawn();
+        world.add_component(idle, Player);
+        world.spawn();
+
+        let mut items = world
+            .query::<(Entity, &Player, Option<&Velocity>)>()
+            .map(|(entity, _, velocity)| (entity, velocity.map(|v| v.0)))
+            .collect::<Vec<_>>();
+        items.sort_by_key(|(entity, _)| *entity);
+
+        assert_eq!(items, vec![(moving, Some(5)), (idle, None)]);
+    }
+
+    #[test]
+    fn query_with_without() {
+        let mut world = World::new();
+        world
+            .register::<Player>()
+            .register::<Enemy>()
+            .register::<Velocity>();
+
+        let mut entities = vec![];
+        for index in 0..4 {
+            let entity = world.spawn();
+            world.add_component(entity, Velocity(index));
+            if index % 2 == 0 {
+                world.add_component(entity, Player);
+            }
+            if index == 0 {
+                world.add_component(entity, Enemy);
+            }
+            entities.push(entity);
+        }
+
+        let with = world
+            .query_filtered::<Entity, With<Player>>()
+            .collect::<Vec<_>>();
+        let without = world
+            .query_filtered::<Entity, Without<Player>>()
+            .collect::<Vec<_>>();
+        assert_eq!(with.len(), 2);
+        assert_eq!(without.len(), 2);
+        assert!(with.iter().all(|entity| !without.contains(entity)));
+
+        let mut all = [with, without].concat();
+        all.sort();
+        assert_eq!(all, entities);
+
+        let players = world
+            .query_filtered::<&Velocity, (With<Player>, Without<Enemy>)>()
+            .map(|velocity| velocity.0)
+            .collect::<Vec<_>>();
+        assert_eq!(players, vec![2]);
+    }
+
+    #[test]
+    fn query_combinations() {
+        let mut world = World::new();
+        world.register::<Score>();
+        for score in 0..4 {
+            let entity = world.spawn();
+            world.add_component(entity, Score(score));
+        }
+
+        let query = world.query::<(Entity, &Score)>();
+        let pairs = query
+            .iter_combinations::<2>()
+            .map(|[(a, _), (b, _)]| (a, b))
+            .collect::<Vec<_>>();
+
+        assert_eq!(pairs.len(), 6);
+        assert!(pairs.iter().all(|(a, b)| a != b));
+        for (index, (a, b)) in pairs.iter().enumerate() {
+            assert!(!pairs[index + 1..].contains(&(*b, *a)));
+        }
+        assert_eq!(query.iter_combinations::<5>().count(), 0);
+        drop(query);
+
+        world.run_system_once(|mut query: Query<&mut Score>| {
+            let mut combinations = query.iter_combinations_mut::<2>();
+            while let Some([a, b]) = combinations.fetch_next() {
+                a.0 += 1;
+                b.0 += 1;
+            }
+        });
+
+        let scores = world.query::<&Score>().map(|s| s.0).collect::<Vec<_>>();
+        assert_eq!(scores, vec![3, 4, 5, 6]);
+    }
+}
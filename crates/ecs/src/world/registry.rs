@@ -1,4 +1,8 @@
-use super::{builtin::events::ComponentUpdate, World};
+use super::{
+    builtin::events::ComponentUpdate,
+    scene::{ComponentSerializer, ResourceSerializer},
+    World,
+};
 use crate::{
     archetype::table::ColumnCell,
     core::{component::Component, entity::Entity, resource::Resource, Type},
@@ -12,11 +16,51 @@ downcast_rs::impl_downcast!(MetadataExtension);
 
 impl MetadataExtension for () {}
 
-#[derive(Clone, Copy)]
+pub type ComponentHook = Box<dyn Fn(&World, Entity) + Send + Sync>;
+
+/// User hooks that run after a component is added to, replaced on or removed from any entity.
+#[derive(Default)]
+pub struct ComponentHooks {
+    on_add: Vec<ComponentHook>,
+    on_replace: Vec<ComponentHook>,
+    on_remove: Vec<ComponentHook>,
+}
+
+impl ComponentHooks {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn on_add(&mut self, hook: impl Fn(&World, Entity) + Send + Sync + 'static) {
+        self.on_add.push(Box::new(hook));
+    }
+
+    pub fn on_replace(&mut self, hook: impl Fn(&World, Entity) + Send + Sync + 'static) {
+        self.on_replace.push(Box::new(hook));
+    }
+
+    pub fn on_remove(&mut self, hook: impl Fn(&World, Entity) + Send + Sync + 'static) {
+        self.on_remove.push(Box::new(hook));
+    }
+
+    pub(crate) fn added(&self, world: &World, entity: Entity) {
+        self.on_add.iter().for_each(|hook| hook(world, entity));
+    }
+
+    pub(crate) fn replaced(&self, world: &World, entity: Entity) {
+        self.on_replace.iter().for_each(|hook| hook(world, entity));
+    }
+
+    pub(crate) fn removed(&self, world: &World, entity: Entity) {
+        self.on_remove.iter().for_each(|hook| hook(world, entity));
+    }
+}
+
 pub struct ComponentExtension {
     on_added: fn(&mut World, Entity),
     on_removed: fn(&mut World, Entity, ColumnCell),
     on_replaced: fn(&mut World, Entity, ColumnCell),
+    hooks: ComponentHooks,
 }
 
 impl ComponentExtension {
@@ -43,24 +87,73 @@ impl ComponentExtension {
                         component: component.into(),
                     });
             },
+            hooks: ComponentHooks::new(),
         }
     }
 
+    pub fn hooks(&self) -> &ComponentHooks {
+        &self.hooks
+    }
+
+    pub fn hooks_mut(&mut self) -> &mut ComponentHooks {
+        &mut self.hooks
+    }
+
     pub fn on_added(&self, world: &mut World, entity: Entity) {
-        (self.on_added)(world, entity)
+        (self.on_added)(world, entity);
+        self.hooks.added(world, entity);
     }
 
     pub fn on_removed(&self, world: &mut World, entity: Entity, component: ColumnCell) {
-        (self.on_removed)(world, entity, component)
+        (self.on_removed)(world, entity, component);
+        self.hooks.removed(world, entity);
     }
 
     pub fn on_replaced(&self, world: &mut World, entity: Entity, component: ColumnCell) {
-        (self.on_replaced)(world, entity, component)
+        (self.on_replaced)(world, entity, component);
+        self.hooks.replaced(world, entity);
     }
 }
 
 impl MetadataExtension for ComponentExtension {}
 
+pub type ResourceHook = Box<dyn Fn(&mut World) + Send + Sync>;
+
+/// User hooks that run after a resource is added to or removed from the world.
+#[derive(Default)]
+pub struct ResourceExtension {
+    on_add: Vec<ResourceHook>,
+    on_remove: Vec<ResourceHook>,
+}
+
+impl ResourceExtension {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn on_add(&mut self, hook: impl Fn(&mut World) + Send + Sync + 'static) {
+        self.on_add.push(Box::new(hook));
+    }
+
+    pub fn on_remove(&mut self, hook: impl Fn(&mut World) + Send + Sync + 'static) {
+        self.on_remove.push(Box::new(hook));
+    }
+
+    pub fn on_added(&self, world: &mut World) {
+        for hook in &self.on_add {
+            hook(world);
+        }
+    }
+
+    pub fn on_removed(&self, world: &mut World) {
+        for hook in &self.on_remove {
+            hook(world);
+        }
+    }
+}
+
+impl MetadataExtension for ResourceExtension {}
+
 pub struct Metadata {
     name: &'static str,
     layout: Layout,
@@ -105,12 +198,16 @@ impl Metadata {
 
 pub struct Registry {
     metadatas: IndexMap<Type, Metadata>,
+    serializers: IndexMap<Type, ComponentSerializer>,
+    resource_serializers: IndexMap<Type, ResourceSerializer>,
 }
 
 impl Registry {
     pub fn new() -> Self {
         Self {
             metadatas: IndexMap::new(),
+            serializers: IndexMap::new(),
+            resource_serializers: IndexMap::new(),
         }
     }
 
@@ -124,12 +221,60 @@ impl Registry {
         self.get(ty).extension_as()
     }
 
+    pub fn get_extension_mut<T: MetadataExtension>(&mut self, ty: &Type) -> &mut T {
+        let metadata = self
+            .metadatas
+            .get_mut(ty)
+            .unwrap_or_else(|| panic!("Type not registered: {:?}", ty));
+        let name = metadata.name;
+        Arc::get_mut(&mut metadata.extension)
+            .and_then(|extension| extension.downcast_mut())
+            .unwrap_or_else(|| panic!("Extension of {} is shared or mismatched", name))
+    }
+
     pub fn register_component<C: Component>(&mut self) -> Type {
         self.register::<C>(ComponentExtension::new::<C>())
     }
 
+    pub fn register_serialize<C>(&mut self) -> Type
+    where
+        C: Component + serde::Serialize + serde::de::DeserializeOwned,
+    {
+        let ty = self.register_component::<C>();
+        self.serializers.insert(ty, ComponentSerializer::new::<C>());
+        ty
+    }
+
+    pub fn serializer(&self, ty: &Type) -> Option<&ComponentSerializer> {
+        self.serializers.get(ty)
+    }
+
+    pub fn serializer_by_name(&self, name: &str) -> Option<(&Type, &ComponentSerializer)> {
+        self.serializers.iter().find(|(_, s)| s.name() == name)
+    }
+
     pub fn register_resource<R: Resource>(&mut self) -> Type {
-        self.register::<R>(())
+        self.register::<R>(ResourceExtension::new())
+    }
+
+    pub fn register_serializable_resource<R>(&mut self) -> Type
+    where
+        R: Resource + Send + serde::Serialize + serde::de::DeserializeOwned,
+    {
+        let ty = self.register_resource::<R>();
+        let serializer = ResourceSerializer::new::<R>();
+        self.resource_serializers.insert(ty, serializer);
+        ty
+    }
+
+    pub fn resource_serializers(&self) -> impl Iterator<Item = &ResourceSerializer> {
+        self.resource_serializers.values()
+    }
+
+    pub fn resource_serializer_by_name(&self, name: &str) -> Option<&ResourceSerializer> {
+        self.resource_serializers
+            .values()
+            .find(|s| s.name() == name)
     }
 
     pub fn index_of(&self, ty: &Type) -> usize {
@@ -0,0 +1,412 @@
+use super::{
+    builtin::{
+        components::{Children, Parent},
+        events::Spawned,
+    },
+    World,
+};
+use crate::{
+    archetype::table::Row,
+    core::{
+        component::{Component, ComponentId},
+        entity::Entity,
+        resource::Resource,
+    },
+};
+use indexmap::IndexMap;
+use serde::{de::DeserializeOwned, Deserialize, Serialize};
+
+#[derive(Clone, Copy)]
+pub struct ComponentSerializer {
+    name: &'static str,
+    serialize: fn(&World, Entity) -> Option<ron::Result<ron::Value>>,
+    deserialize: fn(ron::Value, &mut Row) -> ron::Result<()>,
+}
+
+impl ComponentSerializer {
+    pub fn new<C: Component + Serialize + DeserializeOwned>() -> Self {
+        Self {
+            name: std::any::type_name::<C>(),
+            serialize: |world, entity| {
+                let component = world.get_component::<C>(entity)?;
+                let value = ron::to_string(component)
+                    .and_then(|value| ron::from_str(&value).map_err(|e| e.code));
+                Some(value)
+            },
+            deserialize: |value, row| {
+                row.add_component(value.into_rust::<C>()?);
+                Ok(())
+            },
+        }
+    }
+
+    pub fn name(&self) -> &'static str {
+        self.name
+    }
+
+    pub fn serialize(&self, world: &World, entity: Entity) -> Option<ron::Result<ron::Value>> {
+        (self.serialize)(world, entity)
+    }
+
+    pub fn deserialize(&self, value: ron::Value, row: &mut Row) -> ron::Result<()> {
+        (self.deserialize)(value, row)
+    }
+}
+
+#[derive(Clone, Copy)]
+pub struct ResourceSerializer {
+    name: &'static str,
+    serialize: fn(&World) -> Option<ron::Result<ron::Value>>,
+    deserialize: fn(ron::Value, &mut World) -> ron::Result<()>,
+}
+
+impl ResourceSerializer {
+    pub fn new<R: Resource + Send + Serialize + DeserializeOwned>() -> Self {
+        Self {
+            name: std::any::type_name::<R>(),
+            serialize: |world| {
+                let resource = world.try_resource::<R>()?;
+                let value = ron::to_string(resource)
+                    .and_then(|value| ron::from_str(&value).map_err(|e| e.code));
+                Some(value)
+            },
+            deserialize: |value, world| {
+                world.add_resource(value.into_rust::<R>()?);
+                Ok(())
+            },
+        }
+    }
+
+    pub fn name(&self) -> &'static str {
+        self.name
+    }
+
+    pub fn serialize(&self, world: &World) -> Option<ron::Result<ron::Value>> {
+        (self.serialize)(world)
+    }
+
+    pub fn deserialize(&self, value: ron::Value, world: &mut World) -> ron::Result<()> {
+        (self.deserialize)(value, world)
+    }
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct SceneEntity {
+    pub parent: Option<usize>,
+    pub components: Vec<(String, ron::Value)>,
+}
+
+/// A set of entities and their serializable components.
+/// Entities are stored by their index in the scene, so ids are remapped when spawned.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct Scene {
+    entities: Vec<SceneEntity>,
+    #[serde(skip)]
+    skipped: Vec<String>,
+}
+
+/// The entities spawned from a [`Scene`] in scene order, and the names of the
+/// components that were skipped because they aren't registered for serialization.
+#[derive(Debug, Clone, Default)]
+pub struct SpawnedScene {
+    pub entities: Vec<Entity>,
+    pub skipped: Vec<String>,
+}
+
+impl Scene {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn entities(&self) -> &[SceneEntity] {
+        &self.entities
+    }
+
+    /// Components [`Scene::from_world`] left out because they aren't registered for serialization.
+    pub fn skipped(&self) -> &[String] {
+        &self.skipped
+    }
+
+    pub fn from_world(world: &World, entities: &[Entity]) -> ron::Result<Self> {
+        let indexes = entities
+            .iter()
+            .enumerate()
+            .map(|(index, entity)| (*entity, index))
+            .collect::<IndexMap<_, _>>();
+
+        let registry = world.registry();
+        let mut scene = Scene::new();
+        for entity in entities {
+            let parent = world
+                .get_component::<Parent>(*entity)
+                .and_then(|parent| indexes.get(&**parent).copied());
+
+            let mut components = Vec::new();
+            if let Some(archetype) = world.archetypes().entity_archetype(*entity) {
+                for id in archetype.table().ids() {
+                    if *id == ComponentId::of::<Parent>() || *id == ComponentId::of::<Children>() {
+                        continue;
+                    }
+
+                    match registry.serializer(id) {
+                        Some(serializer) => {
+                            if let Some(value) = serializer.serialize(world, *entity) {
+                                components.push((serializer.name().to_string(), value?));
+                            }
+                        }
+                        None => scene.skipped.push(registry.get(id).name().to_string()),
+                    }
+                }
+            }
+
+            scene.entities.push(SceneEntity { parent, components });
+        }
+
+        Ok(scene)
+    }
+
+    pub fn to_ron(&self) -> ron::Result<String> {
+        ron::ser::to_string_pretty(self, ron::ser::PrettyConfig::default())
+    }
+
+    pub fn from_ron(ron: &str) -> ron::Result<Self> {
+        ron::from_str(ron).map_err(|e| e.code)
+    }
+
+    /// Spawns the scene into the world. Components that aren't registered for
+    /// serialization are skipped and returned by name.
+    pub fn spawn(&self, world: &mut World) -> ron::Result<SpawnedScene> {
+        let mut skipped = vec![];
+        let mut rows = Vec::with_capacity(self.entities.len());
+        for scene_entity in &self.entities {
+            let mut row = Row::new();
+            for (name, value) in &scene_entity.components {
+                match world.registry().serializer_by_name(name) {
+                    Some((_, serializer)) => serializer.deserialize(value.clone(), &mut row)?,
+                    None => skipped.push(name.clone()),
+                }
+            }
+            rows.push(row);
+        }
+
+        let entities = rows
+            .into_iter()
+            .map(|row| {
+                let entity = world.spawn();
+                if let Some(mv) = world.archetypes.add_components(entity, row) {
+                    world.run_component_hooks(entity, mv);
+                }
+                entity
+            })
+            .collect::<Vec<_>>();
+
+        for (scene_entity, entity) in self.entities.iter().zip(&entities) {
+            if let Some(parent) = scene_entity.parent.and_then(|p| entities.get(p)) {
+                world.add_component(*entity, Parent::new(*parent));
+                match world.get_component_mut::<Children>(*parent) {
+                    Some(children) => children.add(*entity),
+                    None => {
+                        let mut children = Children::new();
+                        children.add(*entity);
+                        world.add_component(*parent, children);
+                    }
+                }
+            }
+        }
+
+        world.invoke_event(Spawned::from(entities.clone()));
+
+        Ok(SpawnedScene { entities, skipped })
+    }
+}
+
+/// The serializable resources of a world, keyed by type name.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct ResourceSnapshot {
+    resources: Vec<(String, ron::Value)>,
+}
+
+impl ResourceSnapshot {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn resources(&self) -> &[(String, ron::Value)] {
+        &self.resources
+    }
+
+    pub fn from_world(world: &World) -> ron::Result<Self> {
+        let mut snapshot = Self::new();
+        for serializer in world.registry().resource_serializers() {
+            if let Some(value) = serializer.serialize(world) {
+                let name = serializer.name().to_string();
+                snapshot.resources.push((name, value?));
+            }
+        }
+
+        Ok(snapshot)
+    }
+
+    pub fn to_ron(&self) -> ron::Result<String> {
+        ron::ser::to_string_pretty(self, ron::ser::PrettyConfig::default())
+    }
+
+    pub fn from_ron(ron: &str) -> ron::Result<Self> {
+        ron::from_str(ron).map_err(|e| e.code)
+    }
+
+    /// Replaces the world's resources with the ones in the snapshot and returns
+    /// the names of the resources that were skipped because they aren't registered.
+    pub fn apply(&self, world: &mut World) -> ron::Result<Vec<String>> {
+        let mut skipped = vec![];
+        for (name, value) in &self.resources {
+            match world.registry().resource_serializer_by_name(name).copied() {
+                Some(serializer) => serializer.deserialize(value.clone(), world)?,
+                None => skipped.push(name.clone()),
+            }
+        }
+
+        Ok(skipped)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::Scene;
+    use crate::{
+        core::{component::Component, entity::Entity, resource::Resource},
+        world::{
+            builtin::components::{Children, Parent},
+            World,
+        },
+    };
+    use serde::{Deserialize, Serialize};
+
+    #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+    struct Age(u32);
+    impl Component for Age {}
+
+    #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+    struct Position {
+        x: f32,
+        y: f32,
+    }
+    impl Component for Position {}
+
+    struct Marker;
+    impl Component for Marker {}
+
+    fn spawn(world: &mut World, age: u32, parent: Option<Entity>) -> Entity {
+        let entity = world.spawn();
+        world.add_component(entity, Age(age));
+        world.add_component(entity, Position { x: 1.0, y: 2.0 });
+        world.add_component(entity, Marker);
+        if let Some(parent) = parent {
+            world.add_component(entity, Parent::new(parent));
+            match world.get_component_mut::<Children>(parent) {
+                Some(children) => children.add(entity),
+                None => {
+                    let mut children = Children::new();
+                    children.add(entity);
+                    world.add_component(parent, children);
+                }
+            }
+        }
+
+        entity
+    }
+
+    #[test]
+    fn scene_round_trip() {
+        let mut world = World::new();
+        world
+            .register_serialize::<Age>()
+            .register_serialize::<Position>()
+            .register::<Marker>();
+
+        let root = spawn(&mut world, 1, None);
+        let child = spawn(&mut world, 2, Some(root));
+        let grandchild = spawn(&mut world, 3, Some(child));
+
+        let scene = Scene::from_world(&world, &[root, child, grandchild]).unwrap();
+        let marker = std::any::type_name::<Marker>();
+        assert_eq!(scene.skipped(), [marker, marker, marker]);
+        let ron = scene.to_ron().unwrap();
+
+        for entity in [grandchild, child, root] {
+            world.despawn(entity);
+        }
+
+        let scene = Scene::from_ron(&ron).unwrap();
+        let entities = scene.spawn(&mut world).unwrap().entities;
+        assert!(entities
+            .iter()
+            .all(|e| ![root, child, grandchild].contains(e)));
+        let [root, child, grandchild] = [entities[0], entities[1], entities[2]];
+
+        assert_eq!(world.get_component::<Age>(root), Some(&Age(1)));
+        assert_eq!(world.get_component::<Age>(grandchild), Some(&Age(3)));
+        assert_eq!(
+            world.get_component::<Position>(child),
+            Some(&Position { x: 1.0, y: 2.0 })
+        );
+        assert!(!world.has_component::<Marker>(child));
+
+        assert_eq!(
+            world.get_component::<Parent>(child),
+            Some(&Parent::new(root))
+        );
+        assert_eq!(
+            world.get_component::<Parent>(grandchild),
+            Some(&Parent::new(child))
+        );
+        assert!(world.get_component::<Parent>(root).is_none());
+        assert!(world
+            .get_component::<Children>(child)
+            .is_some_and(|children| children.contains(grandchild)));
+    }
+
+    #[test]
+    fn scene_skips_unregistered() {
+        let mut world = World::new();
+        world.register_serialize::<Age>();
+        let entity = world.spawn();
+        world.add_component(entity, Age(4));
+
+        let ron = Scene::from_world(&world, &[entity])
+            .unwrap()
+            .to_ron()
+            .unwrap();
+        let age = std::any::type_name::<Age>();
+        let ron = ron.replace(age, "Missing");
+
+        let spawned = Scene::from_ron(&ron).unwrap().spawn(&mut world).unwrap();
+        assert_eq!(spawned.skipped, ["Missing"]);
+        assert!(!world.has_component::<Age>(spawned.entities[0]));
+    }
+
+    #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+    struct Score(u32);
+    impl Resource for Score {}
+
+    #[test]
+    fn resource_snapshot() {
+        let mut world = World::new();
+        world
+            .add_resource(Score(10))
+            .register_serializable_resource::<Score>();
+
+        let ron = world.save_resources().unwrap();
+        world.resource_mut::<Score>().0 = 25;
+
+        assert!(world.load_resources(&ron).unwrap().is_empty());
+        assert_eq!(world.resource::<Score>(), &Score(10));
+
+        let score = std::any::type_name::<Score>();
+        let missing = score.replace("Score", "Missing");
+        let unknown = ron.replace(score, &missing);
+        world.resource_mut::<Score>().0 = 25;
+        assert_eq!(world.load_resources(&unknown).unwrap(), [missing]);
+        assert_eq!(world.resource::<Score>(), &Score(25));
+    }
+}
@@ -1,4 +1,4 @@
-use super::{builtin::events::Spawned, cell::WorldCell, registry::ComponentExtension, World};
+use super::{builtin::events::Spawned, cell::WorldCell, World};
 use crate::{
     archetype::{table::Row, Archetypes},
     core::{component::Component, entity::Entity, resource::ResourceId, Type},
@@ -29,28 +29,15 @@ impl<'a> Spawner<'a> {
 impl<'a> Drop for Spawner<'a> {
     fn drop(&mut self) {
         let world = WorldCell::from(&self.world);
-        let registry = world.get().registry();
         let mut spawned = vec![];
         for (entity, components) in self.entities.drain(..) {
-            let mov = match world.get_mut().add_components(entity, components) {
-                Some(mov) => mov,
+            let world = world.get_mut();
+            let mv = match world.archetypes.add_components(entity, components) {
+                Some(mv) => mv,
                 None => continue,
             };
 
-            for id in mov.added {
-                let hooks = registry.get_extension::<ComponentExtension>(&*id);
-                hooks.on_added(world.get_mut(), entity);
-            }
-
-            for (id, component) in mov.replaced {
-                let hooks = registry.get_extension::<ComponentExtension>(&*id);
-                hooks.on_replaced(world.get_mut(), entity, component);
-            }
-
-            for (id, component) in mov.removed {
-                let hooks = registry.get_extension::<ComponentExtension>(&*id);
-                hooks.on_removed(world.get_mut(), entity, component);
-            }
+            world.run_component_hooks(entity, mv);
 
             spawned.push(entity);
         }
@@ -113,3 +100,38 @@ impl<'a, 'b: 'a> Drop for SpawnedEntity<'a, 'b> {
             .insert(self.entity, self.components.take().unwrap());
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::Spawner;
+    use crate::{core::component::Component, world::World};
+
+    struct A;
+    impl Component for A {}
+    struct B;
+    impl Component for B {}
+    struct C;
+    impl Component for C {}
+    struct D;
+    impl Component for D {}
+    struct E;
+    impl Component for E {}
+
+    #[test]
+    fn spawn_single_move() {
+        let mut world = World::new();
+        world
+            .register::<A>()
+            .register::<B>()
+            .register::<C>()
+            .register::<D>()
+            .register::<E>();
+
+        let archetypes = world.archetypes().len();
+        world.run_system_once(|mut spawner: Spawner| {
+            spawner.spawn().with(A).with(B).with(C).with(D).with(E);
+        });
+
+        assert_eq!(world.archetypes().len(), archetypes + 1);
+    }
+}
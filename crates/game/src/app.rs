@@ -1,11 +1,11 @@
 use crate::phases::{Extract, Update};
 use ecs::{
     core::{resource::Resource, IndexMap, Type},
-    system::schedule::Phase,
+    system::{schedule::Phase, SystemArg, WorldAccess},
     task::TaskPool,
-    world::World,
+    world::{cell::WorldCell, World},
 };
-use std::sync::{Arc, Mutex};
+use std::sync::{Arc, Mutex, MutexGuard};
 
 pub trait AppTag: 'static {
     const NAME: &'static str;
@@ -174,6 +174,11 @@ impl Apps {
         &mut self.main.world
     }
 
+    pub fn sub<A: AppTag>(&self) -> Option<MutexGuard<'_, SubApp>> {
+        let app = self.apps.get(&Type::of::<A>())?;
+        Some(app.lock().unwrap())
+    }
+
     pub fn run(&mut self) {
         let main = MainWorld::new(self.main.world_mut());
         for app in self.apps.values_mut() {
@@ -215,14 +220,43 @@ impl Resource for MainWorld {}
 unsafe impl Send for MainWorld {}
 unsafe impl Sync for MainWorld {}
 
+/// Read access to a main world resource from a sub app's extract systems.
+pub struct Main<'a, R: Resource + Send> {
+    resource: &'a R,
+}
+
+impl<'a, R: Resource + Send> std::ops::Deref for Main<'a, R> {
+    type Target = R;
+
+    fn deref(&self) -> &Self::Target {
+        self.resource
+    }
+}
+
+impl<R: Resource + Send> SystemArg for Main<'_, R> {
+    type Item<'a> = Main<'a, R>;
+
+    fn get<'a>(world: &'a WorldCell) -> Self::Item<'a> {
+        let main = world.get().resource::<MainWorld>();
+        Main {
+            resource: main.inner().resource::<R>(),
+        }
+    }
+
+    fn access() -> Vec<WorldAccess> {
+        vec![WorldAccess::resource::<MainWorld>()]
+    }
+}
+
 #[cfg(test)]
 
 mod test {
     use crate::{
-        app::AppTag,
+        app::{AppTag, Main},
         game::Game,
         phases::{Extract, Update},
     };
+    use ecs::core::resource::{ResMut, Resource};
 
     #[test]
     fn sub_app() {
@@ -255,4 +289,37 @@ mod test {
 
         game.run();
     }
+
+    #[test]
+    fn extract_resource() {
+        struct TestApp;
+        impl AppTag for TestApp {
+            const NAME: &'static str = "TestApp";
+        }
+
+        struct Counter(usize);
+        impl Resource for Counter {}
+
+        let mut game = Game::new();
+        game.add_resource(Counter(0))
+            .add_systems(Update, |mut counter: ResMut<Counter>| counter.0 += 1)
+            .add_sub_app::<TestApp>()
+            .add_resource(Counter(0));
+        game.add_extract_system::<TestApp, _>(
+            |main: Main<Counter>, mut counter: ResMut<Counter>| counter.0 = main.0,
+        );
+
+        game.set_runner(|mut game: Game| {
+            game.startup();
+            game.update();
+            game.update();
+
+            let main = game.app().world().resource::<Counter>().0;
+            let app = game.sub_app::<TestApp>().unwrap();
+            assert_eq!(main, 2);
+            assert_eq!(app.world().resource::<Counter>().0, main);
+        });
+
+        game.run();
+    }
 }
@@ -1,9 +1,13 @@
-use std::{error::Error, sync::Arc};
+use std::{
+    error::Error,
+    sync::{Arc, MutexGuard},
+};
 
 use crate::{
-    app::{AppBuilders, AppTag, Apps, MainApp},
-    phases::{Execute, PostExecute, PreExecute, Shutdown, Startup},
+    app::{AppBuilders, AppTag, Apps, MainApp, SubApp},
+    phases::{Execute, Extract, PostExecute, PreExecute, Shutdown, Startup},
     plugin::{Plugin, Plugins},
+    state::{GameState, NextState, State, StateTransitions},
 };
 use ecs::{
     core::{component::Component, resource::Resource},
@@ -99,9 +103,25 @@ impl GameBuilder {
         self
     }
 
+    pub fn add_state<S: GameState>(&mut self, state: S) -> &mut Self {
+        let world = self.apps.main_world_mut();
+        if world.try_resource::<StateTransitions>().is_none() {
+            world.add_resource(StateTransitions::default());
+        }
+
+        world.resource_mut::<StateTransitions>().add::<S>();
+        world
+            .add_resource(State::new(state))
+            .add_resource(NextState::<S>::new());
+        self
+    }
+
     pub fn add_plugin<P: Plugin>(&mut self, plugin: P) -> &mut Self {
-        let mut plugins = plugin.dependencies().flatten();
+        let mut plugins = Plugins::new();
         plugins.add(plugin);
+
+        let mut plugins = plugins.flatten();
+        plugins.retain(|id| !self.plugins.contains(*id));
         for (_, plugin) in plugins.iter_mut() {
             plugin.start(self);
         }
@@ -139,6 +159,14 @@ impl GameBuilder {
         self.apps.add::<A>().world_mut()
     }
 
+    pub fn add_extract_system<A: AppTag, M>(
+        &mut self,
+        systems: impl IntoSystemConfigs<M>,
+    ) -> &mut Self {
+        self.apps.add::<A>().world_mut().add_systems(Extract, systems);
+        self
+    }
+
     pub fn sub_app<A: AppTag>(&self) -> &World {
         self.apps
             .sub::<A>()
@@ -175,6 +203,17 @@ impl GameBuilder {
         self
     }
 
+    pub fn observe_with_priority<E: Event, M>(
+        &mut self,
+        priority: i32,
+        observers: impl IntoSystemConfigs<M>,
+    ) -> &mut Self {
+        self.apps
+            .main_world_mut()
+            .observe_with_priority::<E, M>(priority, observers);
+        self
+    }
+
     pub fn set_runner(&mut self, runner: impl Fn(Game) + 'static) -> &mut Self {
         self.runner = Box::new(runner);
         self
@@ -187,13 +226,19 @@ impl GameBuilder {
 
         self.add_resource(TaskPool::default());
         let apps = self.apps.into_apps();
-        let game = Game { apps };
+        let game = Game {
+            apps,
+            plugins,
+            exiting: false,
+        };
         (self.runner)(game);
     }
 }
 
 pub struct Game {
     apps: Apps,
+    plugins: Plugins,
+    exiting: bool,
 }
 
 impl Game {
@@ -209,11 +254,18 @@ impl Game {
         self.apps.main_app_mut()
     }
 
+    /// Locks a sub app, waiting for its current update to finish.
+    pub fn sub_app<A: AppTag>(&self) -> Option<MutexGuard<'_, SubApp>> {
+        self.apps.sub::<A>()
+    }
+
     pub fn startup(&mut self) {
         self.apps.main_app_mut().run(Startup);
+        StateTransitions::enter(self.apps.main_world_mut());
     }
 
     pub fn update(&mut self) -> Option<ExitGame> {
+        StateTransitions::apply(self.apps.main_world_mut());
         self.apps.main_app_mut().run(PreExecute);
         self.apps.main_app_mut().run(Execute);
         self.apps.run();
@@ -222,11 +274,24 @@ impl Game {
             .apps
             .main_world_mut()
             .resource_mut::<Events<ExitGame>>();
-        events.drain().last()
+        let exit = events.drain().last();
+        self.exiting |= exit.is_some();
+        self.apps.main_world_mut().clear_trackers();
+        exit
+    }
+
+    /// Returns true once an [`ExitGame`] has been dispatched.
+    pub fn exiting(&self) -> bool {
+        self.exiting
     }
 
+    /// Runs the shutdown phase, then cleans up plugins in reverse start order.
     pub fn shutdown(&mut self) {
+        self.exiting = true;
         self.apps.main_app_mut().run(Shutdown);
+
+        let mut plugins = std::mem::take(&mut self.plugins);
+        plugins.cleanup(self);
     }
 
     pub fn flush(&mut self) {
@@ -247,6 +312,15 @@ fn default_runner(mut game: Game) {
     game.shutdown();
 }
 
+/// Updates the game until an [`ExitGame`] is dispatched, then shuts it down.
+pub fn loop_runner(mut game: Game) {
+    game.startup();
+    while !game.exiting() {
+        game.update();
+    }
+    game.shutdown();
+}
+
 #[derive(Debug, Clone)]
 pub enum ExitGame {
     Success,
@@ -278,3 +352,33 @@ impl WorldAction for ExitGame {
 }
 
 impl Event for ExitGame {}
+
+#[cfg(test)]
+mod tests {
+    use super::{loop_runner, ExitGame, Game};
+    use crate::phases::{Shutdown, Update};
+    use ecs::world::action::WorldActions;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+
+    static UPDATES: AtomicUsize = AtomicUsize::new(0);
+    static SHUTDOWNS: AtomicUsize = AtomicUsize::new(0);
+
+    #[test]
+    fn exit_game() {
+        let mut game = Game::new();
+        game.add_systems(Update, |actions: &WorldActions| {
+            if UPDATES.fetch_add(1, Ordering::Relaxed) == 2 {
+                actions.add(ExitGame::success());
+            }
+        })
+        .add_systems(Shutdown, || {
+            SHUTDOWNS.fetch_add(1, Ordering::Relaxed);
+        })
+        .set_runner(loop_runner);
+
+        game.run();
+
+        assert_eq!(UPDATES.load(Ordering::Relaxed), 3);
+        assert_eq!(SHUTDOWNS.load(Ordering::Relaxed), 1);
+    }
+}
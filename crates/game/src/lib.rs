@@ -2,8 +2,10 @@ pub mod app;
 pub mod game;
 pub mod phases;
 pub mod plugin;
+pub mod state;
 
 pub use app::*;
 pub use game::*;
 pub use phases::*;
 pub use plugin::*;
+pub use state::*;
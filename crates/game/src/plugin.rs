@@ -1,4 +1,4 @@
-use crate::game::GameBuilder;
+use crate::game::{Game, GameBuilder};
 use ecs::core::{IndexMap, Type};
 
 pub trait Plugin: 'static {
@@ -6,6 +6,8 @@ pub trait Plugin: 'static {
     fn start(&mut self, _game: &mut GameBuilder) {}
     fn run(&mut self, _game: &mut GameBuilder) {}
     fn finish(&mut self, _game: &mut GameBuilder) {}
+    /// Called once when the game shuts down, after the shutdown phase has run.
+    fn cleanup(&mut self, _game: &mut Game) {}
     fn dependencies(&self) -> Plugins {
         Plugins::new()
     }
@@ -38,13 +40,35 @@ impl Plugins {
         }
     }
 
+    /// Expands dependencies so every plugin comes after the plugins it depends on.
+    /// Panics if the dependencies form a cycle.
     pub fn flatten(&mut self) -> Plugins {
         let mut plugins = Plugins::new();
+        self.flatten_into(&mut plugins, &mut Vec::new());
+        plugins
+    }
+
+    fn flatten_into(&mut self, plugins: &mut Plugins, path: &mut Vec<(PluginId, &'static str)>) {
         for (id, plugin) in self.plugins.drain(..) {
-            plugins.extend(plugin.dependencies().flatten());
+            if let Some(index) = path.iter().position(|(p, _)| *p == id) {
+                let cycle = path[index..]
+                    .iter()
+                    .map(|(_, name)| *name)
+                    .chain(Some(plugin.name()))
+                    .collect::<Vec<_>>();
+                panic!("Plugin dependency cycle detected: {}", cycle.join(" -> "));
+            }
+
+            path.push((id, plugin.name()));
+            plugin.dependencies().flatten_into(plugins, path);
+            path.pop();
+
             plugins.plugins.insert(id, plugin);
         }
-        plugins
+    }
+
+    pub fn retain(&mut self, mut f: impl FnMut(&PluginId) -> bool) {
+        self.plugins.retain(|id, _| f(id));
     }
 
     pub fn extend(&mut self, other: Plugins) {
@@ -90,4 +114,170 @@ impl Plugins {
             plugin.finish(game);
         }
     }
+
+    pub fn cleanup(&mut self, game: &mut Game) {
+        for plugin in self.plugins.values_mut().rev() {
+            plugin.cleanup(game);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{Plugin, Plugins};
+    use crate::game::{Game, GameBuilder};
+    use ecs::core::resource::Resource;
+
+    #[derive(Default)]
+    struct CleanupOrder(Vec<&'static str>);
+    impl Resource for CleanupOrder {}
+
+    #[derive(Default)]
+    struct StartOrder(Vec<&'static str>);
+    impl Resource for StartOrder {}
+
+    fn record(game: &mut GameBuilder, name: &'static str) {
+        match game.try_resource_mut::<StartOrder>() {
+            Some(order) => order.0.push(name),
+            None => {
+                game.add_resource(StartOrder(vec![name]));
+            }
+        }
+    }
+
+    struct A;
+    impl Plugin for A {
+        fn name(&self) -> &'static str {
+            "A"
+        }
+
+        fn start(&mut self, game: &mut GameBuilder) {
+            record(game, self.name());
+        }
+
+        fn dependencies(&self) -> Plugins {
+            let mut plugins = Plugins::new();
+            plugins.add(B);
+            plugins
+        }
+    }
+
+    struct B;
+    impl Plugin for B {
+        fn name(&self) -> &'static str {
+            "B"
+        }
+
+        fn start(&mut self, game: &mut GameBuilder) {
+            record(game, self.name());
+        }
+
+        fn dependencies(&self) -> Plugins {
+            let mut plugins = Plugins::new();
+            plugins.add(C);
+            plugins
+        }
+    }
+
+    struct C;
+    impl Plugin for C {
+        fn name(&self) -> &'static str {
+            "C"
+        }
+
+        fn start(&mut self, game: &mut GameBuilder) {
+            record(game, self.name());
+        }
+    }
+
+    #[test]
+    fn dependency_order() {
+        let mut game = Game::new();
+        game.add_plugin(A);
+        game.add_plugin(C);
+
+        let order = game.try_resource::<StartOrder>().unwrap();
+        assert_eq!(order.0, vec!["C", "B", "A"]);
+    }
+
+    struct Ping;
+    impl Plugin for Ping {
+        fn name(&self) -> &'static str {
+            "Ping"
+        }
+
+        fn dependencies(&self) -> Plugins {
+            let mut plugins = Plugins::new();
+            plugins.add(Pong);
+            plugins
+        }
+    }
+
+    struct Pong;
+    impl Plugin for Pong {
+        fn name(&self) -> &'static str {
+            "Pong"
+        }
+
+        fn dependencies(&self) -> Plugins {
+            let mut plugins = Plugins::new();
+            plugins.add(Ping);
+            plugins
+        }
+    }
+
+    #[test]
+    fn dependency_cycle() {
+        let result = std::panic::catch_unwind(|| {
+            let mut plugins = Plugins::new();
+            plugins.add(Ping);
+            plugins.flatten()
+        });
+
+        let error = result.err().unwrap();
+        let message = error.downcast_ref::<String>().unwrap();
+        assert!(message.contains("Ping"));
+        assert!(message.contains("Pong"));
+    }
+
+    struct Audio;
+    impl Plugin for Audio {
+        fn name(&self) -> &'static str {
+            "Audio"
+        }
+
+        fn cleanup(&mut self, game: &mut Game) {
+            let world = game.app_mut().world_mut();
+            world.resource_mut::<CleanupOrder>().0.push(self.name());
+        }
+    }
+
+    struct Watcher;
+    impl Plugin for Watcher {
+        fn name(&self) -> &'static str {
+            "Watcher"
+        }
+
+        fn cleanup(&mut self, game: &mut Game) {
+            let world = game.app_mut().world_mut();
+            world.resource_mut::<CleanupOrder>().0.push(self.name());
+        }
+    }
+
+    #[test]
+    fn cleanup_order() {
+        Game::new()
+            .add_resource(CleanupOrder::default())
+            .add_plugin(Audio)
+            .add_plugin(Watcher)
+            .set_runner(|mut game: Game| {
+                game.startup();
+                game.shutdown();
+                game.shutdown();
+
+                let order = game.app().world().resource::<CleanupOrder>();
+                assert_eq!(order.0, vec!["Watcher", "Audio"]);
+            })
+            .run();
+    }
 }
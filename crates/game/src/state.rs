@@ -0,0 +1,248 @@
+use ecs::{
+    core::resource::Resource,
+    system::schedule::{Phase, PhaseId, Schedule},
+    world::World,
+};
+use std::{
+    any::{Any, TypeId},
+    collections::HashMap,
+    hash::Hash,
+    sync::{LazyLock, Mutex},
+};
+
+pub trait GameState: Copy + Eq + Hash + Send + Sync + 'static {}
+
+pub struct State<S: GameState> {
+    current: S,
+}
+
+impl<S: GameState> State<S> {
+    pub fn new(state: S) -> Self {
+        Self { current: state }
+    }
+
+    pub fn current(&self) -> S {
+        self.current
+    }
+}
+
+impl<S: GameState> Resource for State<S> {}
+
+/// Requests a transition to a new state. Transitions are applied between frames.
+pub struct NextState<S: GameState> {
+    next: Option<S>,
+}
+
+impl<S: GameState> NextState<S> {
+    pub fn new() -> Self {
+        Self { next: None }
+    }
+
+    pub fn get(&self) -> Option<S> {
+        self.next
+    }
+
+    pub fn set(&mut self, state: S) {
+        self.next = Some(state);
+    }
+
+    pub fn take(&mut self) -> Option<S> {
+        self.next.take()
+    }
+}
+
+impl<S: GameState> Default for NextState<S> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<S: GameState> Resource for NextState<S> {}
+
+pub struct OnEnter<S: GameState>(pub S);
+impl<S: GameState> Phase for OnEnter<S> {
+    fn id(&self) -> PhaseId {
+        state_phase_id::<Self, S>(&self.0)
+    }
+}
+
+pub struct OnExit<S: GameState>(pub S);
+impl<S: GameState> Phase for OnExit<S> {
+    fn id(&self) -> PhaseId {
+        state_phase_id::<Self, S>(&self.0)
+    }
+}
+
+/// Phase ids for each state's `OnEnter`/`OnExit`, handed out from a counter so no two
+/// state phases share one. They share the 32 bit id space with the hashes from
+/// [`PhaseId::of`], so a state phase can still collide with a typed phase.
+static STATE_PHASES: LazyLock<Mutex<StatePhases>> = LazyLock::new(Mutex::default);
+
+#[derive(Default)]
+struct StatePhases {
+    next: u32,
+    ids: HashMap<TypeId, Box<dyn Any + Send>>,
+}
+
+fn state_phase_id<P: Phase, S: GameState>(state: &S) -> PhaseId {
+    let mut phases = STATE_PHASES.lock().unwrap();
+    let StatePhases { next, ids } = &mut *phases;
+    let ids = ids
+        .entry(TypeId::of::<P>())
+        .or_insert_with(|| Box::new(HashMap::<S, PhaseId>::new()))
+        .downcast_mut::<HashMap<S, PhaseId>>()
+        .unwrap();
+
+    *ids.entry(*state).or_insert_with(|| {
+        let id = PhaseId::dynamic(*next);
+        *next += 1;
+        id
+    })
+}
+
+#[derive(Clone, Copy)]
+pub(crate) struct StateTransition {
+    enter: fn(&mut World),
+    apply: fn(&mut World),
+}
+
+impl StateTransition {
+    fn new<S: GameState>() -> Self {
+        Self {
+            enter: |world| {
+                let current = world.resource::<State<S>>().current();
+                run_state_phase(world, OnEnter(current));
+            },
+            apply: |world| {
+                let current = world.resource::<State<S>>().current();
+                match world.resource_mut::<NextState<S>>().take() {
+                    Some(next) if next != current => {
+                        run_state_phase(world, OnExit(current));
+                        world.resource_mut::<State<S>>().current = next;
+                        run_state_phase(world, OnEnter(next));
+                    }
+                    _ => (),
+                }
+            },
+        }
+    }
+}
+
+#[derive(Default)]
+pub(crate) struct StateTransitions {
+    transitions: Vec<(TypeId, StateTransition)>,
+}
+
+impl StateTransitions {
+    /// Registers the transitions for `S`. Adding the same state again does nothing.
+    pub fn add<S: GameState>(&mut self) {
+        let ty = TypeId::of::<S>();
+        if !self.transitions.iter().any(|(t, _)| *t == ty) {
+            self.transitions.push((ty, StateTransition::new::<S>()));
+        }
+    }
+
+    pub fn enter(world: &mut World) {
+        let mut index = 0;
+        while let Some(transition) = Self::get(world, index) {
+            (transition.enter)(world);
+            index += 1;
+        }
+    }
+
+    pub fn apply(world: &mut World) {
+        let mut index = 0;
+        while let Some(transition) = Self::get(world, index) {
+            (transition.apply)(world);
+            index += 1;
+        }
+    }
+
+    fn get(world: &World, index: usize) -> Option<StateTransition> {
+        let states = world.try_resource::<StateTransitions>()?;
+        states.transitions.get(index).map(|(_, t)| *t)
+    }
+}
+
+impl Resource for StateTransitions {}
+
+fn run_state_phase(world: &mut World, phase: impl Phase) {
+    if !world.has_phase(phase.id()) {
+        world.add_schedule(Schedule::new(phase.id()));
+    }
+
+    world.run(phase);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{GameState, NextState, OnEnter, OnExit};
+    use crate::{game::Game, phases::Update};
+    use ecs::{
+        core::resource::{ResMut, Resource},
+        system::schedule::Phase,
+    };
+
+    #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+    enum AppState {
+        Menu,
+        InGame,
+    }
+    impl GameState for AppState {}
+
+    struct Log(Vec<&'static str>);
+    impl Resource for Log {}
+
+    #[test]
+    fn state_transition() {
+        let mut game = Game::new();
+        game.add_state(AppState::Menu)
+            .add_state(AppState::Menu)
+            .add_resource(Log(vec![]))
+            .add_systems(OnEnter(AppState::Menu), |mut log: ResMut<Log>| {
+                log.0.push("enter menu")
+            })
+            .add_systems(OnExit(AppState::Menu), |mut log: ResMut<Log>| {
+                log.0.push("exit menu")
+            })
+            .add_systems(OnEnter(AppState::InGame), |mut log: ResMut<Log>| {
+                log.0.push("enter game")
+            })
+            .add_systems(OnExit(AppState::InGame), |mut log: ResMut<Log>| {
+                log.0.push("exit game")
+            })
+            .add_systems(Update, |mut next: ResMut<NextState<AppState>>| {
+                next.set(AppState::InGame)
+            });
+
+        game.set_runner(|mut game: Game| {
+            game.startup();
+            game.update();
+            game.update();
+            game.update();
+
+            let log = game.app().world().resource::<Log>();
+            assert_eq!(log.0, vec!["enter menu", "exit menu", "enter game"]);
+
+            game.shutdown();
+        });
+
+        game.run();
+    }
+
+    #[test]
+    fn state_phase_ids() {
+        let ids = [
+            OnEnter(AppState::Menu).id(),
+            OnEnter(AppState::InGame).id(),
+            OnExit(AppState::Menu).id(),
+            OnExit(AppState::InGame).id(),
+        ];
+
+        for (index, id) in ids.iter().enumerate() {
+            assert!(!ids[index + 1..].contains(id));
+        }
+
+        assert_eq!(OnEnter(AppState::Menu).id(), ids[0]);
+    }
+}
@@ -6,6 +6,7 @@ use crate::{
         TouchpadPressure, WindowClosed, WindowCreated, WindowDestroyed, WindowFocused, WindowMoved,
         WindowOccluded, WindowResized,
     },
+    frame::{FramePacer, FrameRate, TargetFrameRate},
     window::{Window, WindowConfig},
 };
 use ecs::{
@@ -23,11 +24,15 @@ use winit::{
 
 pub struct App {
     game: Game,
+    pacer: FramePacer,
 }
 
 impl App {
     pub fn new(game: Game) -> Self {
-        Self { game }
+        Self {
+            game,
+            pacer: FramePacer::new(),
+        }
     }
 
     pub fn start(&mut self) {
@@ -35,7 +40,21 @@ impl App {
     }
 
     pub fn update(&mut self) -> Option<ExitGame> {
-        self.game.update()
+        let delta = self.pacer.begin();
+        let world = self.game.app_mut().world_mut();
+        if let Some(frame_rate) = world.try_resource_mut::<FrameRate>() {
+            frame_rate.set_delta(delta);
+        }
+
+        let exit = self.game.update();
+
+        let world = self.game.app_mut().world_mut();
+        let target = world.try_resource::<TargetFrameRate>().copied();
+        if let Some(frame_time) = target.and_then(|target| target.frame_time()) {
+            self.pacer.wait(frame_time);
+        }
+
+        exit
     }
 
     pub fn shutdown(&mut self) {
@@ -92,9 +111,14 @@ impl ApplicationHandler for App {
         }
     }
 
-    fn about_to_wait(&mut self, _: &ActiveEventLoop) {
+    fn about_to_wait(&mut self, event_loop: &ActiveEventLoop) {
+        if self.game.exiting() {
+            return;
+        }
+
         if let Some(exit) = self.update() {
             self.run_event(exit);
+            event_loop.exit();
         }
     }
 
@@ -166,7 +190,18 @@ impl ApplicationHandler for App {
                 stage,
             } => self.run_event(TouchpadPressure::new(device_id, pressure, stage)),
             WindowEvent::Touch(touch) => self.run_event(TouchEvent::from(touch)),
-            WindowEvent::ScaleFactorChanged { scale_factor, .. } => {
+            WindowEvent::ScaleFactorChanged {
+                scale_factor,
+                mut inner_size_writer,
+            } => {
+                let world = self.game.app_mut().world();
+                let logical_size = world
+                    .try_resource::<Window>()
+                    .and_then(Window::logical_size);
+                if let Some(size) = logical_size {
+                    let _ = inner_size_writer.request_inner_size(size.to_physical(scale_factor));
+                }
+
                 self.run_event(ScaleFactorChanged::new(scale_factor))
             }
             WindowEvent::Occluded(occluded) => self.run_event(WindowOccluded::new(occluded)),
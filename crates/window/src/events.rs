@@ -1,7 +1,11 @@
-use crate::{keyboard::KeyCode, winit::{
-    AxisId, DeviceId, ElementState, Force, KeyLocation, Modifiers, MouseButton, PhysicalPosition,
-    PhysicalSize, SmolStr, TouchPhase, WindowId,
-}};
+use crate::{
+    gamepad::{Gamepad, GamepadAxis, GamepadButton},
+    keyboard::KeyCode,
+    winit::{
+        AxisId, DeviceId, ElementState, Force, KeyLocation, Modifiers, MouseButton,
+        PhysicalPosition, PhysicalSize, SmolStr, TouchPhase, WindowId,
+    },
+};
 use ecs::event::Event;
 use std::path::PathBuf;
 
@@ -389,3 +393,68 @@ impl From<winit::event::Touch> for TouchEvent {
 }
 
 impl Event for TouchEvent {}
+
+pub struct GamepadConnected {
+    pub gamepad: Gamepad,
+    pub name: String,
+}
+
+impl GamepadConnected {
+    pub fn new(gamepad: Gamepad, name: impl Into<String>) -> Self {
+        Self {
+            gamepad,
+            name: name.into(),
+        }
+    }
+}
+
+impl Event for GamepadConnected {}
+
+pub struct GamepadDisconnected {
+    pub gamepad: Gamepad,
+}
+
+impl GamepadDisconnected {
+    pub fn new(gamepad: Gamepad) -> Self {
+        Self { gamepad }
+    }
+}
+
+impl Event for GamepadDisconnected {}
+
+pub struct GamepadButtonInput {
+    pub gamepad: Gamepad,
+    pub button: GamepadButton,
+    pub state: ElementState,
+}
+
+impl GamepadButtonInput {
+    pub fn new(gamepad: Gamepad, button: GamepadButton, state: ElementState) -> Self {
+        Self {
+            gamepad,
+            button,
+            state,
+        }
+    }
+}
+
+impl Event for GamepadButtonInput {}
+
+/// An axis moved. Sticks report values in `-1.0..=1.0`, triggers in `0.0..=1.0`.
+pub struct GamepadAxisMotion {
+    pub gamepad: Gamepad,
+    pub axis: GamepadAxis,
+    pub value: f32,
+}
+
+impl GamepadAxisMotion {
+    pub fn new(gamepad: Gamepad, axis: GamepadAxis, value: f32) -> Self {
+        Self {
+            gamepad,
+            axis,
+            value,
+        }
+    }
+}
+
+impl Event for GamepadAxisMotion {}
@@ -0,0 +1,128 @@
+use ecs::core::resource::Resource;
+use std::time::{Duration, Instant};
+
+/// Caps how often the app runner updates the game.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub enum TargetFrameRate {
+    #[default]
+    Uncapped,
+    Fps(u32),
+}
+
+impl TargetFrameRate {
+    pub fn frame_time(&self) -> Option<Duration> {
+        match self {
+            TargetFrameRate::Uncapped | TargetFrameRate::Fps(0) => None,
+            TargetFrameRate::Fps(fps) => Some(Duration::from_secs_f64(1.0 / *fps as f64)),
+        }
+    }
+}
+
+impl Resource for TargetFrameRate {}
+
+/// The measured time between the last two frames.
+#[derive(Debug, Default, Clone, Copy)]
+pub struct FrameRate {
+    delta: Duration,
+}
+
+impl FrameRate {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn delta(&self) -> Duration {
+        self.delta
+    }
+
+    pub fn set_delta(&mut self, delta: Duration) {
+        self.delta = delta;
+    }
+
+    pub fn fps(&self) -> f64 {
+        match self.delta.is_zero() {
+            true => 0.0,
+            false => 1.0 / self.delta.as_secs_f64(),
+        }
+    }
+}
+
+impl Resource for FrameRate {}
+
+/// Holds frames to a target frame time.
+/// Sleeps for most of the remaining time and spins for the rest, since sleeps can overshoot.
+pub struct FramePacer {
+    start: Instant,
+    spin: Duration,
+}
+
+impl FramePacer {
+    pub const DEFAULT_SPIN: Duration = Duration::from_millis(2);
+
+    pub fn new() -> Self {
+        Self {
+            start: Instant::now(),
+            spin: Self::DEFAULT_SPIN,
+        }
+    }
+
+    pub fn with_spin(mut self, spin: Duration) -> Self {
+        self.spin = spin;
+        self
+    }
+
+    /// Starts a new frame and returns the time since the previous one started.
+    pub fn begin(&mut self) -> Duration {
+        let now = Instant::now();
+        let delta = now - self.start;
+        self.start = now;
+        delta
+    }
+
+    /// Returns how long to sleep given the frame time and the time the frame has taken so far.
+    pub fn sleep_time(&self, frame_time: Duration, elapsed: Duration) -> Duration {
+        frame_time.saturating_sub(elapsed).saturating_sub(self.spin)
+    }
+
+    /// Blocks until the current frame has taken at least `frame_time`.
+    pub fn wait(&self, frame_time: Duration) {
+        let sleep = self.sleep_time(frame_time, self.start.elapsed());
+        if !sleep.is_zero() {
+            std::thread::sleep(sleep);
+        }
+
+        while self.start.elapsed() < frame_time {
+            std::hint::spin_loop();
+        }
+    }
+}
+
+impl Default for FramePacer {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{FramePacer, TargetFrameRate};
+    use std::time::Duration;
+
+    #[test]
+    fn frame_sleep_time() {
+        let pacer = FramePacer::new().with_spin(Duration::from_millis(2));
+        let frame_time = TargetFrameRate::Fps(50).frame_time().unwrap();
+        assert_eq!(frame_time, Duration::from_millis(20));
+
+        let sleep = pacer.sleep_time(frame_time, Duration::from_millis(5));
+        assert_eq!(sleep, Duration::from_millis(13));
+
+        let sleep = pacer.sleep_time(frame_time, Duration::from_millis(19));
+        assert_eq!(sleep, Duration::ZERO);
+
+        let sleep = pacer.sleep_time(frame_time, Duration::from_millis(30));
+        assert_eq!(sleep, Duration::ZERO);
+
+        assert_eq!(TargetFrameRate::Uncapped.frame_time(), None);
+    }
+}
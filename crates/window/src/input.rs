@@ -0,0 +1,650 @@
+use crate::{
+    events::{
+        CursorLeft, CursorMoved, GamepadAxisMotion, GamepadButtonInput, GamepadConnected,
+        GamepadDisconnected, KeyEvent, MouseInput, MouseScroll, MouseScrollDelta,
+        ScaleFactorChanged,
+    },
+    gamepad::{Gamepad, GamepadAxis, GamepadButton},
+    keyboard::KeyCode,
+    window::Window,
+    winit::{ElementState, MouseButton},
+};
+use ecs::{
+    core::resource::{Res, ResMut, Resource},
+    event::Events,
+};
+use std::{
+    collections::{HashMap, HashSet},
+    hash::Hash,
+};
+use winit::dpi::{LogicalPosition, PhysicalPosition};
+
+/// Tracks the pressed state of a set of inputs across frames.
+/// `just_pressed` and `just_released` only hold for the frame the transition happened in.
+pub struct Input<T: Copy + Eq + Hash + Send + Sync + 'static> {
+    pressed: HashSet<T>,
+    just_pressed: HashSet<T>,
+    just_released: HashSet<T>,
+}
+
+impl<T: Copy + Eq + Hash + Send + Sync + 'static> Input<T> {
+    pub fn new() -> Self {
+        Self {
+            pressed: HashSet::new(),
+            just_pressed: HashSet::new(),
+            just_released: HashSet::new(),
+        }
+    }
+
+    pub fn pressed(&self, input: T) -> bool {
+        self.pressed.contains(&input)
+    }
+
+    pub fn just_pressed(&self, input: T) -> bool {
+        self.just_pressed.contains(&input)
+    }
+
+    pub fn just_released(&self, input: T) -> bool {
+        self.just_released.contains(&input)
+    }
+
+    pub fn all_pressed(&self, inputs: &[T]) -> bool {
+        inputs.iter().all(|input| self.pressed.contains(input))
+    }
+
+    pub fn any_pressed(&self, inputs: &[T]) -> bool {
+        inputs.iter().any(|input| self.pressed.contains(input))
+    }
+
+    pub fn any_just_pressed(&self, inputs: &[T]) -> bool {
+        inputs.iter().any(|input| self.just_pressed.contains(input))
+    }
+
+    /// True only for the frame the last input of `chord` goes down while the rest are held.
+    pub fn just_pressed_chord(&self, chord: &[T]) -> bool {
+        match chord.split_last() {
+            Some((last, held)) => self.just_pressed(*last) && self.all_pressed(held),
+            None => false,
+        }
+    }
+
+    pub fn iter_pressed(&self) -> impl Iterator<Item = &T> {
+        self.pressed.iter()
+    }
+
+    pub fn press(&mut self, input: T) {
+        if self.pressed.insert(input) {
+            self.just_pressed.insert(input);
+        }
+    }
+
+    pub fn release(&mut self, input: T) {
+        if self.pressed.remove(&input) {
+            self.just_released.insert(input);
+        }
+    }
+
+    /// Clears the per frame transitions while keeping held inputs pressed.
+    pub fn clear(&mut self) {
+        self.just_pressed.clear();
+        self.just_released.clear();
+    }
+
+    pub fn reset(&mut self) {
+        self.pressed.clear();
+        self.clear();
+    }
+}
+
+impl<T: Copy + Eq + Hash + Send + Sync + 'static> Default for Input<T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T: Copy + Eq + Hash + Send + Sync + 'static> Resource for Input<T> {}
+
+pub fn update_key_input(events: Res<Events<KeyEvent>>, mut input: ResMut<Input<KeyCode>>) {
+    for event in events.iter() {
+        match event.state {
+            ElementState::Pressed => input.press(event.code),
+            ElementState::Released => input.release(event.code),
+        }
+    }
+}
+
+/// Aggregated mouse state. Positions and deltas are in logical pixels.
+pub struct Mouse {
+    buttons: Input<MouseButton>,
+    position: Option<LogicalPosition<f64>>,
+    delta: LogicalPosition<f64>,
+    scroll_lines: LogicalPosition<f32>,
+    scroll_pixels: LogicalPosition<f64>,
+    scale_factor: f64,
+}
+
+impl Mouse {
+    pub fn new() -> Self {
+        Self {
+            buttons: Input::new(),
+            position: None,
+            delta: LogicalPosition::new(0.0, 0.0),
+            scroll_lines: LogicalPosition::new(0.0, 0.0),
+            scroll_pixels: LogicalPosition::new(0.0, 0.0),
+            scale_factor: 1.0,
+        }
+    }
+
+    pub fn buttons(&self) -> &Input<MouseButton> {
+        &self.buttons
+    }
+
+    pub fn pressed(&self, button: MouseButton) -> bool {
+        self.buttons.pressed(button)
+    }
+
+    pub fn just_pressed(&self, button: MouseButton) -> bool {
+        self.buttons.just_pressed(button)
+    }
+
+    pub fn just_released(&self, button: MouseButton) -> bool {
+        self.buttons.just_released(button)
+    }
+
+    /// The cursor position, or `None` while the cursor is outside the window.
+    pub fn position(&self) -> Option<LogicalPosition<f64>> {
+        self.position
+    }
+
+    pub fn delta(&self) -> LogicalPosition<f64> {
+        self.delta
+    }
+
+    pub fn scroll_lines(&self) -> LogicalPosition<f32> {
+        self.scroll_lines
+    }
+
+    pub fn scroll_pixels(&self) -> LogicalPosition<f64> {
+        self.scroll_pixels
+    }
+
+    pub fn scale_factor(&self) -> f64 {
+        self.scale_factor
+    }
+
+    pub fn set_scale_factor(&mut self, scale_factor: f64) {
+        self.scale_factor = scale_factor;
+    }
+
+    pub fn move_to(&mut self, position: PhysicalPosition<f64>) {
+        let position = position.to_logical::<f64>(self.scale_factor);
+        if let Some(previous) = self.position {
+            self.delta.x += position.x - previous.x;
+            self.delta.y += position.y - previous.y;
+        }
+
+        self.position = Some(position);
+    }
+
+    pub fn leave(&mut self) {
+        self.position = None;
+    }
+
+    pub fn scroll(&mut self, delta: MouseScrollDelta) {
+        match delta {
+            MouseScrollDelta::Line(lines) => {
+                self.scroll_lines.x += lines.x;
+                self.scroll_lines.y += lines.y;
+            }
+            MouseScrollDelta::Pixel(pixels) => {
+                let pixels = pixels.to_logical::<f64>(self.scale_factor);
+                self.scroll_pixels.x += pixels.x;
+                self.scroll_pixels.y += pixels.y;
+            }
+        }
+    }
+
+    pub fn press(&mut self, button: MouseButton) {
+        self.buttons.press(button);
+    }
+
+    pub fn release(&mut self, button: MouseButton) {
+        self.buttons.release(button);
+    }
+
+    /// Clears the per frame deltas and button transitions.
+    pub fn clear(&mut self) {
+        self.buttons.clear();
+        self.delta = LogicalPosition::new(0.0, 0.0);
+        self.scroll_lines = LogicalPosition::new(0.0, 0.0);
+        self.scroll_pixels = LogicalPosition::new(0.0, 0.0);
+    }
+}
+
+impl Default for Mouse {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Resource for Mouse {}
+
+pub fn update_mouse_buttons(events: Res<Events<MouseInput>>, mut mouse: ResMut<Mouse>) {
+    for event in events.iter() {
+        match event.state {
+            ElementState::Pressed => mouse.press(event.button),
+            ElementState::Released => mouse.release(event.button),
+        }
+    }
+}
+
+pub fn update_mouse_position(events: Res<Events<CursorMoved>>, mut mouse: ResMut<Mouse>) {
+    for event in events.iter() {
+        mouse.move_to(event.position);
+    }
+}
+
+pub fn update_mouse_left(_: Res<Events<CursorLeft>>, mut mouse: ResMut<Mouse>) {
+    mouse.leave();
+}
+
+pub fn update_mouse_scroll(events: Res<Events<MouseScroll>>, mut mouse: ResMut<Mouse>) {
+    for event in events.iter() {
+        mouse.scroll(event.delta);
+    }
+}
+
+pub fn update_mouse_scale_factor(
+    events: Res<Events<ScaleFactorChanged>>,
+    mut mouse: ResMut<Mouse>,
+) {
+    if let Some(event) = events.iter().last() {
+        mouse.set_scale_factor(event.scale_factor);
+    }
+}
+
+pub fn init_mouse_scale_factor(window: Res<Window>, mut mouse: ResMut<Mouse>) {
+    mouse.set_scale_factor(window.scale_factor());
+}
+
+pub fn clear_mouse(mut mouse: ResMut<Mouse>) {
+    mouse.clear();
+}
+
+pub fn clear_input<T: Copy + Eq + Hash + Send + Sync + 'static>(mut input: ResMut<Input<T>>) {
+    input.clear();
+}
+
+pub struct GamepadState {
+    name: String,
+    buttons: Input<GamepadButton>,
+    axes: HashMap<GamepadAxis, f32>,
+}
+
+impl GamepadState {
+    pub fn new(name: impl Into<String>) -> Self {
+        Self {
+            name: name.into(),
+            buttons: Input::new(),
+            axes: HashMap::new(),
+        }
+    }
+
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    pub fn buttons(&self) -> &Input<GamepadButton> {
+        &self.buttons
+    }
+
+    /// The last reported value of the axis, or `0.0` if it hasn't moved yet.
+    pub fn axis(&self, axis: GamepadAxis) -> f32 {
+        self.axes.get(&axis).copied().unwrap_or(0.0)
+    }
+}
+
+/// Aggregated state of every connected gamepad.
+pub struct Gamepads {
+    gamepads: HashMap<Gamepad, GamepadState>,
+}
+
+impl Gamepads {
+    pub fn new() -> Self {
+        Self {
+            gamepads: HashMap::new(),
+        }
+    }
+
+    pub fn get(&self, gamepad: Gamepad) -> Option<&GamepadState> {
+        self.gamepads.get(&gamepad)
+    }
+
+    pub fn is_connected(&self, gamepad: Gamepad) -> bool {
+        self.gamepads.contains_key(&gamepad)
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = (&Gamepad, &GamepadState)> {
+        self.gamepads.iter()
+    }
+
+    pub fn len(&self) -> usize {
+        self.gamepads.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.gamepads.is_empty()
+    }
+
+    pub fn button_pressed(&self, gamepad: Gamepad, button: GamepadButton) -> bool {
+        self.get(gamepad)
+            .is_some_and(|state| state.buttons.pressed(button))
+    }
+
+    pub fn just_pressed(&self, gamepad: Gamepad, button: GamepadButton) -> bool {
+        self.get(gamepad)
+            .is_some_and(|state| state.buttons.just_pressed(button))
+    }
+
+    pub fn just_released(&self, gamepad: Gamepad, button: GamepadButton) -> bool {
+        self.get(gamepad)
+            .is_some_and(|state| state.buttons.just_released(button))
+    }
+
+    pub fn axis(&self, gamepad: Gamepad, axis: GamepadAxis) -> f32 {
+        self.get(gamepad).map_or(0.0, |state| state.axis(axis))
+    }
+
+    pub fn connect(&mut self, gamepad: Gamepad, name: impl Into<String>) {
+        self.gamepads
+            .entry(gamepad)
+            .or_insert_with(|| GamepadState::new(name));
+    }
+
+    pub fn disconnect(&mut self, gamepad: Gamepad) -> Option<GamepadState> {
+        self.gamepads.remove(&gamepad)
+    }
+
+    /// Input from a gamepad that hasn't reported a connection is ignored.
+    pub fn press(&mut self, gamepad: Gamepad, button: GamepadButton) {
+        if let Some(state) = self.gamepads.get_mut(&gamepad) {
+            state.buttons.press(button);
+        }
+    }
+
+    pub fn release(&mut self, gamepad: Gamepad, button: GamepadButton) {
+        if let Some(state) = self.gamepads.get_mut(&gamepad) {
+            state.buttons.release(button);
+        }
+    }
+
+    pub fn set_axis(&mut self, gamepad: Gamepad, axis: GamepadAxis, value: f32) {
+        if let Some(state) = self.gamepads.get_mut(&gamepad) {
+            state.axes.insert(axis, value);
+        }
+    }
+
+    /// Clears the per frame button transitions of every gamepad.
+    pub fn clear(&mut self) {
+        for state in self.gamepads.values_mut() {
+            state.buttons.clear();
+        }
+    }
+}
+
+impl Default for Gamepads {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Resource for Gamepads {}
+
+pub fn update_gamepad_connected(
+    events: Res<Events<GamepadConnected>>,
+    mut gamepads: ResMut<Gamepads>,
+) {
+    for event in events.iter() {
+        gamepads.connect(event.gamepad, event.name.clone());
+    }
+}
+
+pub fn update_gamepad_disconnected(
+    events: Res<Events<GamepadDisconnected>>,
+    mut gamepads: ResMut<Gamepads>,
+) {
+    for event in events.iter() {
+        gamepads.disconnect(event.gamepad);
+    }
+}
+
+pub fn update_gamepad_buttons(
+    events: Res<Events<GamepadButtonInput>>,
+    mut gamepads: ResMut<Gamepads>,
+) {
+    for event in events.iter() {
+        match event.state {
+            ElementState::Pressed => gamepads.press(event.gamepad, event.button),
+            ElementState::Released => gamepads.release(event.gamepad, event.button),
+        }
+    }
+}
+
+pub fn update_gamepad_axes(events: Res<Events<GamepadAxisMotion>>, mut gamepads: ResMut<Gamepads>) {
+    for event in events.iter() {
+        gamepads.set_axis(event.gamepad, event.axis, event.value);
+    }
+}
+
+pub fn clear_gamepads(mut gamepads: ResMut<Gamepads>) {
+    gamepads.clear();
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{
+        clear_input, clear_mouse, update_key_input, update_mouse_position, Gamepads, Input, Mouse,
+    };
+    use crate::{
+        events::{
+            CursorMoved, GamepadAxisMotion, GamepadButtonInput, GamepadConnected,
+            GamepadDisconnected, KeyEvent,
+        },
+        gamepad::{Gamepad, GamepadAxis, GamepadButton},
+        keyboard::KeyCode,
+        plugin::GamepadPlugin,
+        winit::{DeviceId, ElementState, KeyLocation, PhysicalPosition},
+    };
+    use ecs::event::{Event, Events};
+    use game::{Game, Last};
+
+    fn key_event(code: KeyCode, state: ElementState) -> KeyEvent {
+        KeyEvent {
+            device: DeviceId::dummy(),
+            code,
+            text: None,
+            location: KeyLocation::Standard,
+            state,
+            repeat: false,
+            synthetic: false,
+        }
+    }
+
+    fn send<E: Event>(game: &mut Game, event: E) {
+        let world = game.app_mut().world_mut();
+        world.resource_mut::<Events<E>>().add(event);
+        game.flush_type::<E>();
+    }
+
+    #[test]
+    fn key_input() {
+        let mut game = Game::new();
+        game.register_event::<KeyEvent>()
+            .add_resource(Input::<KeyCode>::new())
+            .observe::<KeyEvent, _>(update_key_input)
+            .add_systems(Last, clear_input::<KeyCode>);
+
+        game.set_runner(|mut game: Game| {
+            game.startup();
+
+            send(&mut game, key_event(KeyCode::Space, ElementState::Pressed));
+            let input = game.app().world().resource::<Input<KeyCode>>();
+            assert!(input.pressed(KeyCode::Space));
+            assert!(input.just_pressed(KeyCode::Space));
+            game.update();
+
+            let input = game.app().world().resource::<Input<KeyCode>>();
+            assert!(input.pressed(KeyCode::Space));
+            assert!(!input.just_pressed(KeyCode::Space));
+            game.update();
+
+            send(&mut game, key_event(KeyCode::Space, ElementState::Released));
+            let input = game.app().world().resource::<Input<KeyCode>>();
+            assert!(!input.pressed(KeyCode::Space));
+            assert!(input.just_released(KeyCode::Space));
+            game.update();
+
+            let input = game.app().world().resource::<Input<KeyCode>>();
+            assert!(!input.just_released(KeyCode::Space));
+
+            game.shutdown();
+        });
+
+        game.run();
+    }
+
+    #[test]
+    fn key_chord() {
+        let mut game = Game::new();
+        game.register_event::<KeyEvent>()
+            .add_resource(Input::<KeyCode>::new())
+            .observe::<KeyEvent, _>(update_key_input)
+            .add_systems(Last, clear_input::<KeyCode>);
+
+        game.set_runner(|mut game: Game| {
+            game.startup();
+
+            let chord = [KeyCode::ControlLeft, KeyCode::KeyS];
+            send(&mut game, key_event(KeyCode::KeyS, ElementState::Pressed));
+            let input = game.app().world().resource::<Input<KeyCode>>();
+            assert!(!input.just_pressed_chord(&chord));
+            game.update();
+
+            send(&mut game, key_event(KeyCode::KeyS, ElementState::Released));
+            send(
+                &mut game,
+                key_event(KeyCode::ControlLeft, ElementState::Pressed),
+            );
+            let input = game.app().world().resource::<Input<KeyCode>>();
+            assert!(input.any_just_pressed(&chord));
+            assert!(!input.just_pressed_chord(&chord));
+            game.update();
+
+            let mut fired = 0;
+            send(&mut game, key_event(KeyCode::KeyS, ElementState::Pressed));
+            for _ in 0..3 {
+                let input = game.app().world().resource::<Input<KeyCode>>();
+                assert!(input.all_pressed(&chord));
+                if input.just_pressed_chord(&chord) {
+                    fired += 1;
+                }
+                game.update();
+            }
+
+            assert_eq!(fired, 1);
+            game.shutdown();
+        });
+
+        game.run();
+    }
+
+    #[test]
+    fn mouse_delta() {
+        let mut game = Game::new();
+        game.register_event::<CursorMoved>()
+            .add_resource(Mouse::new())
+            .observe::<CursorMoved, _>(update_mouse_position)
+            .add_systems(Last, clear_mouse);
+
+        game.set_runner(|mut game: Game| {
+            game.startup();
+
+            let device = DeviceId::dummy();
+            send(
+                &mut game,
+                CursorMoved::new(device, PhysicalPosition::new(5.0, 5.0)),
+            );
+            game.update();
+
+            send(
+                &mut game,
+                CursorMoved::new(device, PhysicalPosition::new(15.0, 10.0)),
+            );
+            let mouse = game.app().world().resource::<Mouse>();
+            assert_eq!(mouse.delta().x, 10.0);
+            assert_eq!(mouse.delta().y, 5.0);
+            game.update();
+
+            let mouse = game.app().world().resource::<Mouse>();
+            assert_eq!(mouse.delta().x, 0.0);
+            assert_eq!(mouse.delta().y, 0.0);
+            assert_eq!(mouse.position().map(|p| p.x), Some(15.0));
+
+            game.shutdown();
+        });
+
+        game.run();
+    }
+
+    #[test]
+    fn gamepad_input() {
+        let mut game = Game::new();
+        game.add_plugin(GamepadPlugin);
+
+        game.set_runner(|mut game: Game| {
+            game.startup();
+
+            let pad = Gamepad::new(0);
+            let button = GamepadButton::South;
+            send(&mut game, GamepadConnected::new(pad, "Test Pad"));
+            send(
+                &mut game,
+                GamepadButtonInput::new(pad, button, ElementState::Pressed),
+            );
+            send(
+                &mut game,
+                GamepadAxisMotion::new(pad, GamepadAxis::LeftStickX, 0.5),
+            );
+            let gamepads = game.app().world().resource::<Gamepads>();
+            assert_eq!(gamepads.get(pad).map(|p| p.name()), Some("Test Pad"));
+            assert!(gamepads.button_pressed(pad, button));
+            assert!(gamepads.just_pressed(pad, button));
+            assert_eq!(gamepads.axis(pad, GamepadAxis::LeftStickX), 0.5);
+            assert_eq!(gamepads.axis(pad, GamepadAxis::LeftStickY), 0.0);
+            game.update();
+
+            let gamepads = game.app().world().resource::<Gamepads>();
+            assert!(gamepads.button_pressed(pad, button));
+            assert!(!gamepads.just_pressed(pad, button));
+            assert_eq!(gamepads.axis(pad, GamepadAxis::LeftStickX), 0.5);
+
+            send(
+                &mut game,
+                GamepadButtonInput::new(pad, button, ElementState::Released),
+            );
+            let gamepads = game.app().world().resource::<Gamepads>();
+            assert!(!gamepads.button_pressed(pad, button));
+            assert!(gamepads.just_released(pad, button));
+            game.update();
+
+            send(&mut game, GamepadDisconnected::new(pad));
+            let gamepads = game.app().world().resource::<Gamepads>();
+            assert!(!gamepads.is_connected(pad));
+            assert!(gamepads.is_empty());
+
+            game.shutdown();
+        });
+
+        game.run();
+    }
+}
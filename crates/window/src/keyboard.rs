@@ -11,6 +11,7 @@ pub enum NativeKeyCode {
     Xkb(u32),
 }
 
+#[derive(Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
 pub enum KeyCode {
     /// Unidentified key.
     Unknown,
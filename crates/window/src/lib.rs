@@ -1,5 +1,8 @@
 pub mod app;
 pub mod events;
+pub mod frame;
+pub mod gamepad;
+pub mod input;
 pub mod keyboard;
 pub mod plugin;
 pub mod window;
@@ -2,14 +2,22 @@ use crate::{
     app::App,
     events::{
         AxisMotion, CursorEntered, CursorLeft, CursorMoved, DoubleTapGesture, DroppedFile,
-        HoveredFile, HoveredFileCancelled, KeyEvent, ModifiersChanged, MouseInput, MouseScroll,
-        PanGesture, PinchGesture, RotationGesture, ScaleFactorChanged, TouchpadPressure,
-        WindowClosed, WindowCreated, WindowDestroyed, WindowFocused, WindowMoved, WindowOccluded,
-        WindowResized,
+        GamepadAxisMotion, GamepadButtonInput, GamepadConnected, GamepadDisconnected, HoveredFile,
+        HoveredFileCancelled, KeyEvent, ModifiersChanged, MouseInput, MouseScroll, PanGesture,
+        PinchGesture, RotationGesture, ScaleFactorChanged, TouchpadPressure, WindowClosed,
+        WindowCreated, WindowDestroyed, WindowFocused, WindowMoved, WindowOccluded, WindowResized,
     },
+    frame::FrameRate,
+    input::{
+        clear_gamepads, clear_input, clear_mouse, init_mouse_scale_factor, update_gamepad_axes,
+        update_gamepad_buttons, update_gamepad_connected, update_gamepad_disconnected,
+        update_key_input, update_mouse_buttons, update_mouse_left, update_mouse_position,
+        update_mouse_scale_factor, update_mouse_scroll, Gamepads, Input, Mouse,
+    },
+    keyboard::KeyCode,
     window::WindowConfig,
 };
-use game::{GameBuilder, Plugin};
+use game::{GameBuilder, Last, Plugin};
 
 pub struct WindowPlugin;
 
@@ -43,6 +51,18 @@ impl Plugin for WindowPlugin {
             .register_event::<TouchpadPressure>()
             .register_event::<ScaleFactorChanged>()
             .register_event::<WindowOccluded>()
+            .add_resource(Input::<KeyCode>::new())
+            .observe::<KeyEvent, _>(update_key_input)
+            .add_systems(Last, clear_input::<KeyCode>)
+            .add_resource(Mouse::new())
+            .observe::<WindowCreated, _>(init_mouse_scale_factor)
+            .observe::<ScaleFactorChanged, _>(update_mouse_scale_factor)
+            .observe::<CursorMoved, _>(update_mouse_position)
+            .observe::<CursorLeft, _>(update_mouse_left)
+            .observe::<MouseScroll, _>(update_mouse_scroll)
+            .observe::<MouseInput, _>(update_mouse_buttons)
+            .add_systems(Last, clear_mouse)
+            .add_resource(FrameRate::new())
             .set_runner(App::runner);
     }
 
@@ -52,3 +72,26 @@ impl Plugin for WindowPlugin {
         }
     }
 }
+
+/// Aggregates gamepad events into the `Gamepads` resource. This only aggregates:
+/// no backend polls controllers yet, so the gamepad events must be sent by the game.
+pub struct GamepadPlugin;
+
+impl Plugin for GamepadPlugin {
+    fn name(&self) -> &'static str {
+        "Gamepad"
+    }
+
+    fn start(&mut self, game: &mut GameBuilder) {
+        game.register_event::<GamepadConnected>()
+            .register_event::<GamepadDisconnected>()
+            .register_event::<GamepadButtonInput>()
+            .register_event::<GamepadAxisMotion>()
+            .add_resource(Gamepads::new())
+            .observe::<GamepadConnected, _>(update_gamepad_connected)
+            .observe::<GamepadDisconnected, _>(update_gamepad_disconnected)
+            .observe::<GamepadButtonInput, _>(update_gamepad_buttons)
+            .observe::<GamepadAxisMotion, _>(update_gamepad_axes)
+            .add_systems(Last, clear_gamepads);
+    }
+}
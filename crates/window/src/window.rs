@@ -1,9 +1,46 @@
-use ecs::core::resource::Resource;
-use winit::{event_loop::ActiveEventLoop, window::WindowId};
+use ecs::{
+    core::resource::Resource,
+    world::{action::WorldAction, World},
+};
+use winit::{
+    dpi::{LogicalSize, PhysicalSize},
+    event_loop::ActiveEventLoop,
+    monitor::MonitorHandle,
+    window::{Fullscreen, WindowId},
+};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum FullscreenMode {
+    /// Borderless fullscreen on the current monitor.
+    Borderless,
+    /// Exclusive fullscreen using the first video mode of the current monitor.
+    Exclusive,
+}
+
+impl FullscreenMode {
+    fn into_fullscreen(self, monitor: Option<MonitorHandle>) -> Option<Fullscreen> {
+        match self {
+            FullscreenMode::Borderless => Some(Fullscreen::Borderless(monitor)),
+            FullscreenMode::Exclusive => monitor
+                .and_then(|monitor| monitor.video_modes().next())
+                .map(Fullscreen::Exclusive),
+        }
+    }
+}
+
+/// The units `WindowConfig`'s width and height are given in.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum SizeUnit {
+    #[default]
+    Physical,
+    /// Scaled by the monitor's scale factor, and kept when the scale factor changes.
+    Logical,
+}
 
 pub struct WindowConfig {
     pub width: u32,
     pub height: u32,
+    pub unit: SizeUnit,
     pub title: String,
     pub resizable: bool,
     pub visible: bool,
@@ -11,6 +48,7 @@ pub struct WindowConfig {
     pub transparent: bool,
     pub maximized: bool,
     pub decorations: bool,
+    pub fullscreen: Option<FullscreenMode>,
 }
 
 impl WindowConfig {
@@ -18,6 +56,7 @@ impl WindowConfig {
         Self {
             width: 800,
             height: 600,
+            unit: SizeUnit::Physical,
             title: title.to_string(),
             resizable: true,
             visible: true,
@@ -25,6 +64,7 @@ impl WindowConfig {
             transparent: false,
             maximized: false,
             decorations: true,
+            fullscreen: None,
         }
     }
 
@@ -39,6 +79,27 @@ impl WindowConfig {
         self
     }
 
+    pub fn with_logical_size(mut self, width: u32, height: u32) -> Self {
+        self.width = width;
+        self.height = height;
+        self.unit = SizeUnit::Logical;
+        self
+    }
+
+    pub fn logical_size(&self) -> Option<LogicalSize<u32>> {
+        match self.unit {
+            SizeUnit::Physical => None,
+            SizeUnit::Logical => Some(LogicalSize::new(self.width, self.height)),
+        }
+    }
+
+    pub fn physical_size(&self, scale_factor: f64) -> PhysicalSize<u32> {
+        match self.logical_size() {
+            Some(size) => size.to_physical(scale_factor),
+            None => PhysicalSize::new(self.width, self.height),
+        }
+    }
+
     pub fn with_resizable(mut self, resizable: bool) -> Self {
         self.resizable = resizable;
         self
@@ -68,28 +129,49 @@ impl WindowConfig {
         self.decorations = decorations;
         self
     }
+
+    pub fn with_fullscreen(mut self, fullscreen: Option<FullscreenMode>) -> Self {
+        self.fullscreen = fullscreen;
+        self
+    }
 }
 
 impl Resource for WindowConfig {}
 
 pub struct Window {
     inner: winit::window::Window,
+    logical_size: Option<LogicalSize<u32>>,
 }
 
 impl Window {
     pub fn new(config: WindowConfig, event_loop: &ActiveEventLoop) -> Self {
+        let scale_factor = event_loop
+            .primary_monitor()
+            .map(|monitor| monitor.scale_factor())
+            .unwrap_or(1.0);
+        let logical_size = config.logical_size();
+        let size = config.physical_size(scale_factor);
+
         let attributes = winit::window::Window::default_attributes()
             .with_title(config.title)
-            .with_inner_size(winit::dpi::PhysicalSize::new(config.width, config.height))
+            .with_inner_size(size)
             .with_resizable(config.resizable)
             .with_visible(config.visible)
             .with_transparent(config.transparent)
             .with_maximized(config.maximized)
-            .with_decorations(config.decorations);
+            .with_decorations(config.decorations)
+            .with_fullscreen(
+                config
+                    .fullscreen
+                    .and_then(|mode| mode.into_fullscreen(event_loop.primary_monitor())),
+            );
 
         let window = event_loop.create_window(attributes).unwrap();
 
-        Self { inner: window }
+        Self {
+            inner: window,
+            logical_size,
+        }
     }
 
     pub fn id(&self) -> WindowId {
@@ -100,9 +182,24 @@ impl Window {
         self.inner.inner_size()
     }
 
+    pub fn scale_factor(&self) -> f64 {
+        self.inner.scale_factor()
+    }
+
+    /// The requested size in logical pixels, if the window was configured with one.
+    pub fn logical_size(&self) -> Option<LogicalSize<u32>> {
+        self.logical_size
+    }
+
     pub fn inner(&self) -> &winit::window::Window {
         &self.inner
     }
+
+    pub fn set_fullscreen(&self, fullscreen: Option<FullscreenMode>) {
+        let monitor = self.inner.current_monitor();
+        self.inner
+            .set_fullscreen(fullscreen.and_then(|mode| mode.into_fullscreen(monitor)));
+    }
 }
 
 impl std::ops::Deref for Window {
@@ -114,3 +211,27 @@ impl std::ops::Deref for Window {
 }
 
 impl Resource for Window {}
+
+pub struct SetFullscreen(pub Option<FullscreenMode>);
+
+impl WorldAction for SetFullscreen {
+    fn execute(self, world: &mut World) -> Option<()> {
+        world.try_resource::<Window>()?.set_fullscreen(self.0);
+        Some(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::WindowConfig;
+    use winit::dpi::PhysicalSize;
+
+    #[test]
+    fn logical_window_size() {
+        let config = WindowConfig::new("Window").with_logical_size(800, 600);
+        assert_eq!(config.physical_size(2.0), PhysicalSize::new(1600, 1200));
+
+        let config = WindowConfig::new("Window").with_size(800, 600);
+        assert_eq!(config.physical_size(2.0), PhysicalSize::new(800, 600));
+    }
+}